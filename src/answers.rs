@@ -0,0 +1,167 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, InputError};
+
+/// Expected answers for the recorded puzzle inputs, one `DAY PART VALUE` entry per line.
+///
+/// Newlines and backslashes in values are escaped as `\n` and `\\`, so multi-line answers such
+/// as day 10's rendered screen fit in a single entry. Blank lines and lines starting with `#`
+/// are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AnswerFile {
+    entries: BTreeMap<(u8, u8), String>,
+}
+
+impl AnswerFile {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.txt")
+    }
+
+    /// Loads the file at `path`, treating a missing file as having no answers.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents).map_err(|err| err.with_file(path))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, InputError> {
+        let mut entries = BTreeMap::new();
+
+        for (text, number) in contents.lines().zip(1..) {
+            if text.trim().is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: &str| InputError::new(number, 1, text, message);
+
+            let mut fields = text.splitn(3, ' ');
+            let day = fields
+                .next()
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| error("Invalid day"))?;
+            let part = fields
+                .next()
+                .and_then(|part| part.parse::<u8>().ok())
+                .filter(|part| matches!(part, 1 | 2))
+                .ok_or_else(|| error("Invalid part"))?;
+            let answer = fields
+                .next()
+                .map(unescape)
+                .ok_or_else(|| error("Missing answer"))?;
+
+            if entries.insert((day, part), answer).is_some() {
+                return Err(error("Duplicated answer"));
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: impl Into<String>) {
+        self.entries.insert((day, part), answer.into());
+    }
+}
+
+impl Display for AnswerFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((day, part), answer) in &self.entries {
+            writeln!(f, "{day:02} {part} {}", escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        };
+        escaped
+    })
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut answers = AnswerFile::default();
+        answers.set(1, 1, "24000");
+        answers.set(10, 2, "##..\n#.\\.");
+        answers.set(5, 2, "MCD");
+
+        let contents = answers.to_string();
+        assert_eq!(
+            contents,
+            indoc! {r"
+                01 1 24000
+                05 2 MCD
+                10 2 ##..\n#.\\.
+            "}
+        );
+        assert_eq!(AnswerFile::parse(&contents), Ok(answers));
+    }
+
+    #[test]
+    fn test_multiline() {
+        let mut answers = AnswerFile::default();
+        answers.set(10, 2, "##..\n.##.");
+        let parsed = AnswerFile::parse(&answers.to_string()).unwrap();
+        assert_eq!(parsed.get(10, 2), Some("##..\n.##."));
+        assert_eq!(parsed.get(10, 1), None);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let contents = indoc! {"
+            # day part answer
+            01 1 24000
+            01 3 45000
+        "};
+        let err = AnswerFile::parse(contents).unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (3, "Invalid part"));
+
+        let err = AnswerFile::parse("01 1 1\n01 1 2\n").unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "Duplicated answer"));
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Config, Diagnostics, Error, Input, InputError, Mode, Solution, Source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "stddev_ns", with = "nanos")]
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Summarizes a non-empty set of samples; the standard deviation is the sample one.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (n.max(2) - 1) as f64;
+
+        Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub phase: Phase,
+    pub iterations: usize,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+pub(crate) fn bench<S: Solution>(
+    source: Source,
+    mode: Mode,
+    config: Option<&Config>,
+    options: BenchOptions,
+) -> Result<Vec<Timing>, Error> {
+    let input = Input::read(&source, &Diagnostics::new(mode))?.with_config(config.cloned());
+    let with_file = |err: InputError| err.with_file(source.to_string());
+
+    let parse = || S::parse(&input).map_err(with_file);
+    let parsed = parse()?;
+    let timing = |phase, samples: Vec<Duration>| Timing {
+        day: S::DAY,
+        phase,
+        iterations: samples.len(),
+        stats: Stats::from_samples(&samples),
+    };
+
+    Ok(vec![
+        timing(Phase::Parse, measure(options, parse)?),
+        timing(
+            Phase::Part1,
+            measure(options, || S::part1(&parsed)).map_err(with_file)?,
+        ),
+        timing(
+            Phase::Part2,
+            measure(options, || S::part2(&parsed)).map_err(with_file)?,
+        ),
+    ])
+}
+
+fn measure<T, E>(
+    options: BenchOptions,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<Vec<Duration>, E> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+
+    (0..options.iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            let result = black_box(f()?);
+            let elapsed = start.elapsed();
+            drop(result);
+            Ok(elapsed)
+        })
+        .collect()
+}
+
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Stats::from_samples(&[ms(7)]);
+        assert_eq!((stats.min, stats.median, stats.mean), (ms(7), ms(7), ms(7)));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_json() {
+        let timing = Timing {
+            day: 8,
+            phase: Phase::Part2,
+            iterations: 4,
+            stats: Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]),
+        };
+        let json = serde_json::to_string(&timing).unwrap();
+        assert_eq!(
+            json,
+            r#"{"day":8,"phase":"part2","iterations":4,"min_ns":1000000,"median_ns":2500000,"mean_ns":2500000,"stddev_ns":1290994}"#
+        );
+        assert_eq!(serde_json::from_str::<Timing>(&json).unwrap(), timing);
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+};
+
+use advent::{iter::IteratorExt, Input, InputError, Order, ReportOptions, Reporting, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Parsed<'a> = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = u128;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(calories: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(calories))
+    }
+
+    fn part2(calories: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(calories))
+    }
+}
+
+impl Reporting for Day01 {
+    fn report(calories: &Self::Parsed<'_>, options: &ReportOptions) -> Result<String, InputError> {
+        Ok(Report { calories, options }.to_string())
+    }
+}
+
+fn part1(calories: &[usize]) -> usize {
+    calories.iter().copied().max().unwrap_or_default()
+}
+
+/// Summed in `u128`, which cannot overflow for three `usize` totals.
+fn part2(calories: &[usize]) -> u128 {
+    top_k(calories, 3)
+        .into_iter()
+        .map(|(_, total)| total as u128)
+        .sum()
+}
+
+/// The `k` elves carrying the most calories as `(elf, total)`, most first; ties go to the
+/// earlier elf.
+fn top_k(calories: &[usize], k: usize) -> Vec<(usize, usize)> {
+    calories
+        .iter()
+        .enumerate()
+        .map(|(elf, total)| (*total, Reverse(elf)))
+        .top_k(k)
+        .into_iter()
+        .map(|(total, Reverse(elf))| (elf, total))
+        .collect()
+}
+
+/// Per-elf totals and their distribution, with elves numbered from 1.
+struct Report<'a> {
+    calories: &'a [usize],
+    options: &'a ReportOptions,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(&max) = self.calories.iter().max() else {
+            return writeln!(f, "No elves");
+        };
+
+        let mut elves = top_k(
+            self.calories,
+            self.options.top.unwrap_or(self.calories.len()),
+        );
+        if self.options.order == Order::Ascending {
+            elves.reverse();
+        }
+
+        writeln!(f, "Elf   Calories")?;
+        for (elf, total) in elves {
+            writeln!(f, "{:<5} {total:>8}", elf + 1)?;
+        }
+
+        let mut sorted = self.calories.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0,
+            _ => sorted[n / 2] as f64,
+        };
+        // Nearest-rank percentiles.
+        let percentile = |p: usize| sorted[(p * n).div_ceil(100).max(1) - 1];
+
+        writeln!(f)?;
+        writeln!(f, "Elves: {n}")?;
+        writeln!(
+            f,
+            "Total: {}",
+            sorted.iter().map(|&total| total as u128).sum::<u128>()
+        )?;
+        writeln!(f, "Median: {median}")?;
+        writeln!(
+            f,
+            "Percentiles: p10 {} p25 {} p50 {} p75 {} p90 {}",
+            percentile(10),
+            percentile(25),
+            percentile(50),
+            percentile(75),
+            percentile(90)
+        )?;
+
+        let tied = (1..)
+            .zip(self.calories)
+            .filter(|(_, total)| **total == max)
+            .map(|(elf, _)| elf.to_string())
+            .collect::<Vec<_>>();
+        writeln!(f, "Most calories: {max} (elf {})", tied.join(", "))
+    }
+}
+
+fn parse(input: &Input) -> Result<Vec<usize>, InputError> {
+    let diagnostics = input.diagnostics();
+
+    input
+        .blocks()
+        .map(|block| {
+            let elf = block.index + 1;
+            let mut total = 0usize;
+
+            for line in block.lines() {
+                let column = line.len() - line.trim_start().len() + 1;
+                let calories = line.trim().parse::<usize>().map_err(|err| {
+                    line.error(column, format!("Invalid calories for elf {elf}: {err}"))
+                });
+
+                if let Some(calories) = diagnostics.check(calories)? {
+                    total = total.checked_add(calories).ok_or_else(|| {
+                        line.error(column, format!("Total calories of elf {elf} overflow"))
+                    })?;
+                }
+            }
+
+            Ok(total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::{Diagnostics, Mode};
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        1000
+        2000
+        3000
+
+        4000
+
+        5000
+        6000
+
+        7000
+        8000
+        9000
+
+        10000
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 24000);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 45000);
+    }
+
+    #[test]
+    fn test_separators() {
+        // Whitespace-only lines separate elves, CRLF endings are accepted and the last elf
+        // needs no trailing blank line.
+        let input = "1000\r\n2000\r\n  \r\n3000\r\n\t\n\n 4000 \n5000";
+        assert_eq!(parse(&Input::from(input)), Ok(vec![3000, 3000, 9000]));
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let err = parse(&Input::from("1000\n\n2000\n10OO0\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+        assert_eq!(
+            err.message,
+            "Invalid calories for elf 2: invalid digit found in string"
+        );
+
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new("1000\n10OO0\n\n2000\n", diagnostics.clone());
+        assert_eq!(parse(&input), Ok(vec![1000, 2000]));
+        assert_eq!(diagnostics.skipped().len(), 1);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = format!("1\n\n{}\n  1\n", usize::MAX);
+        let err = parse(&Input::from(input.as_str())).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+        assert_eq!(err.message, "Total calories of elf 2 overflow");
+
+        let calories = vec![usize::MAX; 3];
+        assert_eq!(part2(&calories), usize::MAX as u128 * 3);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let calories = [5, 9, 2, 9, 7];
+        assert_eq!(top_k(&calories, 3), vec![(1, 9), (3, 9), (4, 7)]);
+        assert_eq!(top_k(&calories, 0), vec![]);
+        assert_eq!(top_k(&calories, 9).len(), 5);
+    }
+
+    #[test]
+    fn test_report() {
+        let calories = parse(&Input::from(INPUT)).unwrap();
+        let report = |options| {
+            Report {
+                calories: &calories,
+                options: &options,
+            }
+            .to_string()
+        };
+
+        assert_eq!(
+            report(ReportOptions::default()),
+            indoc! {"
+                Elf   Calories
+                4        24000
+                3        11000
+                5        10000
+                1         6000
+                2         4000
+
+                Elves: 5
+                Total: 55000
+                Median: 10000
+                Percentiles: p10 4000 p25 6000 p50 10000 p75 11000 p90 24000
+                Most calories: 24000 (elf 4)
+            "}
+        );
+
+        let options = ReportOptions {
+            top: Some(2),
+            order: Order::Ascending,
+        };
+        assert!(report(options).starts_with("Elf   Calories\n3        11000\n4        24000\n\n"));
+    }
+
+    #[test]
+    fn test_report_ties() {
+        let options = ReportOptions::default();
+        let report = Report {
+            calories: &[3, 8, 8, 1],
+            options: &options,
+        };
+        let report = report.to_string();
+        assert!(report.contains("Median: 5.5\n"));
+        assert!(report.ends_with("Most calories: 8 (elf 2, 3)\n"));
+    }
+}
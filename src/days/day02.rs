@@ -0,0 +1,862 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fmt::{self, Display},
+    ops::Deref,
+    str::FromStr,
+};
+
+use advent::{Config, Entry, Input, InputError, Line, Order, ReportOptions, Reporting, Solution};
+use itertools::Itertools;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Parsed<'a> = Guide;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(guide: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(guide))
+    }
+
+    fn part2(guide: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(guide))
+    }
+}
+
+impl Reporting for Day02 {
+    fn report(guide: &Self::Parsed<'_>, options: &ReportOptions) -> Result<String, InputError> {
+        Ok(Report { guide, options }.to_string())
+    }
+}
+
+/// The rules of the puzzle, used unless the input comes with its own.
+const CLASSIC: &str = "\
+weapons = rock, paper, scissors
+opponent.A = rock
+opponent.B = paper
+opponent.C = scissors
+part1.X = rock
+part1.Y = paper
+part1.Z = scissors
+part2.X = loss
+part2.Y = draw
+part2.Z = win
+";
+
+/// A weapon, as its position in the weapons of a [`Game`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Weapon(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const ALL: [Self; 3] = [Self::Loss, Self::Draw, Self::Win];
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "loss" => Ok(Self::Loss),
+            "draw" => Ok(Self::Draw),
+            "win" => Ok(Self::Win),
+            _ => Err(format!("Unknown outcome {s}")),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loss => f.write_str("loss"),
+            Self::Draw => f.write_str("draw"),
+            Self::Win => f.write_str("win"),
+        }
+    }
+}
+
+/// Points for the weapon played, by position, and for the outcome of a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoring {
+    pub weapons: Vec<usize>,
+    pub loss: usize,
+    pub draw: usize,
+    pub win: usize,
+}
+
+impl Scoring {
+    /// The puzzle's scoring: the weapon's position from 1, and 0, 3 or 6 for the outcome.
+    pub fn standard(weapons: usize) -> Self {
+        Self {
+            weapons: (1..=weapons).collect(),
+            loss: 0,
+            draw: 3,
+            win: 6,
+        }
+    }
+
+    fn outcome(&self, outcome: Outcome) -> usize {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+/// Weapons and which beats which, as a tournament: of two distinct weapons, exactly one wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    /// Row-major matrix telling whether the row weapon beats the column one.
+    beats: Vec<bool>,
+    scoring: Scoring,
+}
+
+impl Game {
+    /// Builds a game from `(winner, loser)` pairs of weapon positions.
+    pub fn new<S: Into<String>>(
+        names: impl IntoIterator<Item = S>,
+        wins: impl IntoIterator<Item = (usize, usize)>,
+        scoring: Scoring,
+    ) -> Result<Self, String> {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let n = names.len();
+        if scoring.weapons.len() != n {
+            return Err(format!(
+                "Expected scores for {n} weapons, found {}",
+                scoring.weapons.len()
+            ));
+        }
+        let mut beats = vec![false; n * n];
+
+        for (winner, loser) in wins {
+            if winner >= n || loser >= n {
+                return Err(format!("Unknown weapon in {winner} beats {loser}"));
+            }
+            if winner == loser || beats[loser * n + winner] {
+                return Err(format!(
+                    "Contradictory rule: {} beats {}",
+                    names[winner], names[loser]
+                ));
+            }
+            beats[winner * n + loser] = true;
+        }
+
+        for a in 0..n {
+            for b in a + 1..n {
+                if !beats[a * n + b] && !beats[b * n + a] {
+                    return Err(format!("No winner between {} and {}", names[a], names[b]));
+                }
+            }
+        }
+
+        Ok(Self {
+            names,
+            beats,
+            scoring,
+        })
+    }
+
+    /// A balanced game where every weapon beats the weapons an odd distance before it in the list,
+    /// wrapping around, as in rock-paper-scissors or rock-paper-scissors-Spock-lizard.
+    pub fn cyclic<S: Into<String>>(
+        names: impl IntoIterator<Item = S>,
+        scoring: Scoring,
+    ) -> Result<Self, String> {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return Err(format!(
+                "A cyclic game needs an odd number of weapons, not {n}"
+            ));
+        }
+
+        let wins = (0..n).flat_map(|winner| {
+            (1..n)
+                .step_by(2)
+                .map(move |distance| (winner, (winner + n - distance) % n))
+        });
+        Self::new(names, wins.collect::<Vec<_>>(), scoring)
+    }
+
+    pub fn name(&self, weapon: Weapon) -> &str {
+        &self.names[weapon.0]
+    }
+
+    pub fn weapons(&self) -> impl Iterator<Item = Weapon> {
+        (0..self.names.len()).map(Weapon)
+    }
+
+    pub fn outcome(&self, me: Weapon, opponent: Weapon) -> Outcome {
+        let n = self.names.len();
+        if self.beats[me.0 * n + opponent.0] {
+            Outcome::Win
+        } else if self.beats[opponent.0 * n + me.0] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// The weapon to play against `opponent` to get `outcome`; when several do, the one scoring
+    /// the most, then the first.
+    pub fn weapon_for_outcome(&self, opponent: Weapon, outcome: Outcome) -> Option<Weapon> {
+        self.weapons()
+            .filter(|me| self.outcome(*me, opponent) == outcome)
+            .min_by_key(|me| Reverse(self.scoring.weapons[me.0]))
+    }
+
+    pub fn score(&self, me: Weapon, opponent: Weapon) -> usize {
+        self.scoring.weapons[me.0] + self.scoring.outcome(self.outcome(me, opponent))
+    }
+}
+
+/// What the symbols of a guide stand for, and how rounds are scored.
+///
+/// Rules are read from `key = value` lines, with `#` starting a comment:
+/// - `weapons` lists the weapons of a [cyclic](Game::cyclic) game, separated by commas;
+/// - `opponent.SYMBOL` gives the weapon an opponent symbol stands for;
+/// - `part1.SYMBOL` and `part2.SYMBOL` give the weapon, then the outcome, a response stands for;
+/// - `score.NAME` overrides the points of a weapon or of `loss`, `draw` or `win`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    game: Game,
+    opponents: HashMap<String, Weapon>,
+    responses: Vec<String>,
+    part1: Mapping,
+    part2: Mapping,
+}
+
+/// An entry of the rules, with its key split into a section and a symbol.
+struct Rule<'a> {
+    entry: Entry<'a>,
+    section: &'a str,
+    symbol: &'a str,
+}
+
+impl<'a> Deref for Rule<'a> {
+    type Target = Entry<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entry
+    }
+}
+
+impl Rules {
+    pub fn classic() -> Self {
+        Self::parse(&Config::new("classic rules", CLASSIC)).expect("classic rules are valid")
+    }
+
+    pub fn parse(config: &Config) -> Result<Self, InputError> {
+        let mut entries = Vec::new();
+
+        for entry in config.entries()? {
+            let key = entry.key;
+            let (section, symbol) = key.split_once('.').unwrap_or((key, ""));
+            match section {
+                "weapons" if !key.contains('.') => {}
+                "opponent" | "part1" | "part2" | "score" => {
+                    if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+                        return Err(entry.error(key, format!("Invalid symbol {symbol:?}")));
+                    }
+                }
+                _ => return Err(entry.error(key, format!("Unknown key {key}"))),
+            }
+            entries.push(Rule {
+                entry,
+                section,
+                symbol,
+            });
+        }
+
+        let weapons = entries
+            .iter()
+            .find(|entry| entry.key == "weapons")
+            .ok_or_else(|| config.missing("Missing weapons"))?;
+        let names = weapons.value.split(',').map(str::trim).collect::<Vec<_>>();
+        if names.len() < 3 {
+            return Err(weapons.error(weapons.value, "A game needs at least 3 weapons"));
+        }
+        if let Some(name) = names
+            .iter()
+            .find(|name| name.is_empty() || name.contains('.'))
+        {
+            return Err(weapons.error(weapons.value, format!("Invalid weapon name {name:?}")));
+        }
+        if let Some(name) = names.iter().duplicates().next() {
+            return Err(weapons.error(weapons.value, format!("Duplicate weapon {name}")));
+        }
+        // `score.NAME` could not tell such a weapon from the outcome.
+        if let Some(name) = names.iter().find(|name| name.parse::<Outcome>().is_ok()) {
+            return Err(weapons.error(name, format!("Weapon {name} is named like an outcome")));
+        }
+
+        let weapon = |entry: &Rule, name: &str| {
+            names
+                .iter()
+                .position(|weapon| *weapon == name)
+                .map(Weapon)
+                .ok_or_else(|| entry.error(name, format!("Unknown weapon {name}")))
+        };
+
+        let mut scoring = Scoring::standard(names.len());
+        let mut opponents = HashMap::new();
+        let mut part1 = Vec::new();
+        let mut part2 = Vec::new();
+
+        for entry in &entries {
+            match entry.section {
+                "opponent" => {
+                    opponents.insert(entry.symbol.to_owned(), weapon(entry, entry.value)?);
+                }
+                "part1" => part1.push((entry, weapon(entry, entry.value)?)),
+                "part2" => {
+                    let outcome = entry
+                        .value
+                        .parse::<Outcome>()
+                        .map_err(|err| entry.error(entry.value, err))?;
+                    part2.push((entry, outcome));
+                }
+                "score" => {
+                    let points = entry.parse::<usize>()?;
+                    match entry.symbol.parse::<Outcome>() {
+                        Ok(Outcome::Loss) => scoring.loss = points,
+                        Ok(Outcome::Draw) => scoring.draw = points,
+                        Ok(Outcome::Win) => scoring.win = points,
+                        Err(_) => scoring.weapons[weapon(entry, entry.symbol)?.0] = points,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let game = Game::cyclic(names.iter().copied(), scoring)
+            .map_err(|err| weapons.error(weapons.value, err))?;
+
+        for weapon in game.weapons() {
+            let name = names[weapon.0];
+            if !opponents.values().contains(&weapon) {
+                return Err(weapons.error(name, format!("No opponent symbol for {name}")));
+            }
+            if !part1.iter().any(|(_, response)| *response == weapon) {
+                return Err(weapons.error(name, format!("No part 1 response for {name}")));
+            }
+        }
+        if let Some(outcome) = Outcome::ALL
+            .into_iter()
+            .find(|outcome| !part2.iter().any(|(_, response)| response == outcome))
+        {
+            return Err(config.missing(format!("No part 2 response for {outcome}")));
+        }
+
+        for (entry, _) in &part2 {
+            if !part1.iter().any(|(other, _)| other.symbol == entry.symbol) {
+                return Err(entry.error(entry.key, "Response has no part 1 meaning"));
+            }
+        }
+        let outcomes = part1
+            .iter()
+            .map(|(entry, _)| {
+                part2
+                    .iter()
+                    .find(|(other, _)| other.symbol == entry.symbol)
+                    .map(|(_, outcome)| *outcome)
+                    .ok_or_else(|| entry.error(entry.key, "Response has no part 2 meaning"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            game,
+            opponents,
+            responses: part1
+                .iter()
+                .map(|(entry, _)| entry.symbol.to_owned())
+                .collect(),
+            part1: Mapping::Weapons(part1.into_iter().map(|(_, weapon)| weapon).collect()),
+            part2: Mapping::Outcomes(outcomes),
+        })
+    }
+
+    fn round(&self, line: &Line<'_>) -> Result<Round, InputError> {
+        let column = |symbol: &str| symbol.as_ptr() as usize - line.text.as_ptr() as usize + 1;
+        let (opponent, response) = line
+            .split_whitespace()
+            .collect_tuple()
+            .ok_or_else(|| line.error(1, "Expected an opponent and a response"))?;
+
+        let weapon = self.opponents.get(opponent).ok_or_else(|| {
+            line.error(
+                column(opponent),
+                format!("Unknown opponent symbol {opponent}"),
+            )
+        })?;
+        let index = self
+            .responses
+            .iter()
+            .position(|symbol| symbol == response)
+            .ok_or_else(|| {
+                line.error(
+                    column(response),
+                    format!("Unknown response symbol {response}"),
+                )
+            })?;
+        Ok(Round(*weapon, index))
+    }
+}
+
+/// An opponent's weapon and the position of the response among those of the [`Rules`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round(Weapon, usize);
+
+#[derive(Debug)]
+pub struct Guide {
+    rules: Rules,
+    rounds: Vec<Round>,
+}
+
+/// What the second column of the guide stands for, by response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mapping {
+    Weapons(Vec<Weapon>),
+    Outcomes(Vec<Outcome>),
+}
+
+impl Mapping {
+    /// Every assignment of the responses to distinct weapons, then to distinct outcomes.
+    fn all(rules: &Rules) -> impl Iterator<Item = Self> + '_ {
+        let responses = rules.responses.len();
+        let weapons = rules
+            .game
+            .weapons()
+            .permutations(responses)
+            .map(Self::Weapons);
+        let outcomes = Outcome::ALL
+            .into_iter()
+            .permutations(responses)
+            .map(Self::Outcomes);
+        weapons.chain(outcomes)
+    }
+
+    fn play(&self, game: &Game, Round(opponent, response): &Round) -> Option<Weapon> {
+        match self {
+            Self::Weapons(weapons) => Some(weapons[*response]),
+            Self::Outcomes(outcomes) => game.weapon_for_outcome(*opponent, outcomes[*response]),
+        }
+    }
+
+    fn score(&self, game: &Game, rounds: &[Round]) -> usize {
+        rounds
+            .iter()
+            .filter_map(|round| Some(game.score(self.play(game, round)?, round.0)))
+            .sum()
+    }
+
+    fn describe(&self, rules: &Rules) -> String {
+        let meanings = match self {
+            Self::Weapons(weapons) => weapons
+                .iter()
+                .map(|weapon| rules.game.name(*weapon).to_string())
+                .collect::<Vec<_>>(),
+            Self::Outcomes(outcomes) => outcomes.iter().map(Outcome::to_string).collect(),
+        };
+        rules
+            .responses
+            .iter()
+            .zip(meanings)
+            .map(|(response, meaning)| format!("{response}={meaning}"))
+            .join(" ")
+    }
+}
+
+fn part1(guide: &Guide) -> usize {
+    guide.rules.part1.score(&guide.rules.game, &guide.rounds)
+}
+
+fn part2(guide: &Guide) -> usize {
+    guide.rules.part2.score(&guide.rules.game, &guide.rounds)
+}
+
+/// The guide's score under every mapping of its second column.
+struct Report<'a> {
+    guide: &'a Guide,
+    options: &'a ReportOptions,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules = &self.guide.rules;
+        let scores = Mapping::all(rules)
+            .map(|mapping| {
+                let score = mapping.score(&rules.game, &self.guide.rounds);
+                (mapping, score)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = scores.iter().collect::<Vec<_>>();
+        rows.sort_by_key(|(_, score)| Reverse(*score));
+        rows.truncate(self.options.top.unwrap_or(rows.len()));
+        if self.options.order == Order::Ascending {
+            rows.reverse();
+        }
+
+        let rows = rows
+            .into_iter()
+            .map(|(mapping, score)| (mapping.describe(rules), score))
+            .collect::<Vec<_>>();
+        let width = rows
+            .iter()
+            .map(|(mapping, _)| mapping.len())
+            .max()
+            .unwrap_or(0);
+        writeln!(f, "{:<width$} {:>8}", "Mapping", "Score")?;
+        for (mapping, score) in rows {
+            writeln!(f, "{mapping:<width$} {score:>8}")?;
+        }
+
+        writeln!(f)?;
+        let (weapons, outcomes): (Vec<_>, Vec<_>) = scores
+            .iter()
+            .partition(|(mapping, _)| matches!(mapping, Mapping::Weapons(_)));
+        for (kind, scores) in [("weapons", weapons), ("outcomes", outcomes)] {
+            // Ties go to the mapping listed first.
+            let best = scores.iter().rev().max_by_key(|(_, score)| score);
+            let worst = scores.iter().min_by_key(|(_, score)| score);
+            for (label, extreme) in [("Best", best), ("Worst", worst)] {
+                match extreme {
+                    Some((mapping, score)) => writeln!(
+                        f,
+                        "{label} as {kind}: {} ({score})",
+                        mapping.describe(rules)
+                    )?,
+                    None => writeln!(f, "{label} as {kind}: none")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse(input: &Input) -> Result<Guide, InputError> {
+    let rules = match input.config() {
+        Some(config) => Rules::parse(config).map_err(|err| err.with_file(config.path()))?,
+        None => Rules::classic(),
+    };
+
+    let diagnostics = input.diagnostics();
+    let rounds = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| diagnostics.check(rules.round(&line)).transpose())
+        .collect::<Result<_, _>>()?;
+
+    Ok(Guide { rules, rounds })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        A Y
+        B X
+        C Z
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_mappings() {
+        let rules = Rules::classic();
+        let mappings = Mapping::all(&rules).collect::<Vec<_>>();
+        assert_eq!(mappings.len(), 12);
+        assert_eq!(mappings[0], rules.part1);
+        assert_eq!(mappings[6], rules.part2);
+    }
+
+    #[test]
+    fn test_report() {
+        let guide = parse(&Input::from(INPUT)).unwrap();
+        let report = |options| {
+            Report {
+                guide: &guide,
+                options: &options,
+            }
+            .to_string()
+        };
+
+        let options = ReportOptions {
+            top: Some(3),
+            order: Order::Descending,
+        };
+        assert_eq!(
+            report(options),
+            indoc! {"
+                Mapping                      Score
+                X=scissors Y=paper Z=rock       24
+                X=win Y=loss Z=draw             18
+                X=rock Y=paper Z=scissors       15
+
+                Best as weapons: X=scissors Y=paper Z=rock (24)
+                Worst as weapons: X=rock Y=scissors Z=paper (6)
+                Best as outcomes: X=win Y=loss Z=draw (18)
+                Worst as outcomes: X=loss Y=draw Z=win (12)
+            "}
+        );
+
+        let options = ReportOptions {
+            top: None,
+            order: Order::Ascending,
+        };
+        assert!(report(options).starts_with(
+            "Mapping                      Score\nX=rock Y=scissors Z=paper        6\n"
+        ));
+    }
+
+    const GERMAN: &str = indoc! {"
+        # Schere, Stein, Papier
+        weapons = Stein, Papier, Schere
+        opponent.St = Stein
+        opponent.P = Papier
+        opponent.Sc = Schere
+        part1.1 = Stein
+        part1.2 = Papier
+        part1.3 = Schere
+        part2.1 = loss
+        part2.2 = draw
+        part2.3 = win
+        score.win = 10  # more for a win
+    "};
+
+    fn with_rules(input: &str, rules: &str) -> Result<Guide, InputError> {
+        parse(&Input::from(input).with_config(Some(Config::new("rules.txt", rules))))
+    }
+
+    #[test]
+    fn test_rules() {
+        let guide = with_rules("St 2\nP 1\n\nSc 3\n", GERMAN).unwrap();
+        assert_eq!(part1(&guide), 19);
+        assert_eq!(part2(&guide), 16);
+    }
+
+    #[test]
+    fn test_rpsls_rules() {
+        let rules = indoc! {"
+            weapons = rock, paper, scissors, spock, lizard
+            opponent.A = rock
+            opponent.B = paper
+            opponent.C = scissors
+            opponent.D = spock
+            opponent.E = lizard
+            part1.V = rock
+            part1.W = paper
+            part1.X = scissors
+            part1.Y = spock
+            part1.Z = lizard
+            part2.V = loss
+            part2.W = loss
+            part2.X = draw
+            part2.Y = win
+            part2.Z = win
+        "};
+        let guide = with_rules("A Y\nE W\nD V\n", rules).unwrap();
+        // Spock vaporizes rock, lizard eats paper and spock vaporizes rock.
+        assert_eq!(part1(&guide), 10 + 2 + 1);
+        // Winning against rock and losing against lizard favour spock over paper, and losing
+        // against spock favours scissors over rock.
+        assert_eq!(part2(&guide), 10 + 4 + 3);
+        // Responses outnumber the outcomes, so only weapon mappings can be enumerated.
+        assert_eq!(Mapping::all(&guide.rules).count(), 120);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let error = |rules: &str| {
+            let err = with_rules("", rules).unwrap_err();
+            assert_eq!(err.file, Some("rules.txt".into()));
+            (err.line, err.column, err.message)
+        };
+        let replace = |from, to| GERMAN.replacen(from, to, 1);
+
+        assert_eq!(
+            error(&replace("opponent.P = Papier", "opponent.St = Papier")),
+            (
+                4,
+                1,
+                "Duplicate key opponent.St, already defined on line 3".into()
+            )
+        );
+        assert_eq!(
+            error(&replace("opponent.Sc = Schere", "")),
+            (2, 26, "No opponent symbol for Schere".into())
+        );
+        assert_eq!(
+            error(&replace("part2.3 = win", "")),
+            (13, 1, "No part 2 response for win".into())
+        );
+        assert_eq!(
+            error(&replace("part2.3 = win", "part2.4 = win")),
+            (11, 1, "Response has no part 1 meaning".into())
+        );
+        assert_eq!(
+            error(&replace("part2.1 = loss", "part2.1 = lose")),
+            (9, 11, "Unknown outcome lose".into())
+        );
+        assert_eq!(
+            error(&replace("part1.2 = Papier", "part1.2 = Paper")),
+            (7, 11, "Unknown weapon Paper".into())
+        );
+        assert_eq!(
+            error(&replace("score.win", "score.tie")),
+            (12, 7, "Unknown weapon tie".into())
+        );
+        assert_eq!(
+            error(&replace("weapons", "weapon")),
+            (2, 1, "Unknown key weapon".into())
+        );
+        assert_eq!(
+            error(&replace(", Schere", ", Schere, Brunnen")),
+            (
+                2,
+                11,
+                "A cyclic game needs an odd number of weapons, not 4".into()
+            )
+        );
+        assert_eq!(
+            error(&replace("Papier, Schere", "Papier, draw")),
+            (2, 26, "Weapon draw is named like an outcome".into())
+        );
+        assert_eq!(
+            error(&replace("part1.3 = Schere", "part1.3 Schere")),
+            (8, 1, "Expected key = value".into())
+        );
+    }
+
+    #[test]
+    fn test_invalid_round() {
+        let err = parse(&Input::from("A Y\nB Q\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "Unknown response symbol Q");
+
+        let err = with_rules("A 1\n", GERMAN).unwrap_err();
+        assert_eq!(err.message, "Unknown opponent symbol A");
+        assert_eq!(err.file, None);
+    }
+
+    fn rpsls() -> Game {
+        let weapons = ["rock", "paper", "scissors", "spock", "lizard"];
+        Game::cyclic(weapons, Scoring::standard(5)).unwrap()
+    }
+
+    #[test]
+    fn test_rps() {
+        let rps = Rules::classic().game;
+        let [rock, paper, scissors] = [0, 1, 2].map(Weapon);
+        assert_eq!(rps.outcome(paper, rock), Outcome::Win);
+        assert_eq!(rps.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(rps.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rps.weapon_for_outcome(rock, Outcome::Loss), Some(scissors));
+        assert_eq!(rps.score(rock, scissors), 7);
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let game = rpsls();
+        let [rock, paper, scissors, spock, lizard] = [0, 1, 2, 3, 4].map(Weapon);
+        for (winner, loser) in [
+            (scissors, paper),
+            (paper, rock),
+            (rock, lizard),
+            (lizard, spock),
+            (spock, scissors),
+            (scissors, lizard),
+            (lizard, paper),
+            (paper, spock),
+            (spock, rock),
+            (rock, scissors),
+        ] {
+            assert_eq!(game.outcome(winner, loser), Outcome::Win);
+            assert_eq!(game.outcome(loser, winner), Outcome::Loss);
+        }
+
+        // Both spock and paper beat rock; spock scores more.
+        assert_eq!(game.weapon_for_outcome(rock, Outcome::Win), Some(spock));
+        assert_eq!(game.weapon_for_outcome(rock, Outcome::Draw), Some(rock));
+    }
+
+    #[test]
+    fn test_cyclic_balance() {
+        for n in [1, 3, 5, 7, 9] {
+            let game = Game::cyclic((0..n).map(|i| i.to_string()), Scoring::standard(n)).unwrap();
+            for opponent in game.weapons() {
+                let wins = game
+                    .weapons()
+                    .filter(|me| game.outcome(*me, opponent) == Outcome::Win)
+                    .count();
+                assert_eq!(wins, (n - 1) / 2);
+                for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                    let me = game.weapon_for_outcome(opponent, outcome);
+                    let expected = (n > 1 || outcome == Outcome::Draw).then_some(outcome);
+                    assert_eq!(me.map(|me| game.outcome(me, opponent)), expected);
+                }
+            }
+        }
+
+        assert!(Game::cyclic(["rock", "paper"], Scoring::standard(2)).is_err());
+    }
+
+    #[test]
+    fn test_invalid_game() {
+        assert_eq!(
+            Game::new(["a", "b", "c"], [(0, 1), (1, 2)], Scoring::standard(3)),
+            Err("No winner between a and c".into())
+        );
+        assert_eq!(
+            Game::new(["a", "b"], [(0, 1), (1, 0)], Scoring::standard(2)),
+            Err("Contradictory rule: b beats a".into())
+        );
+        assert!(Game::new(["a", "b"], [(0, 2)], Scoring::standard(2)).is_err());
+    }
+
+    #[test]
+    fn test_scoring() {
+        let scoring = Scoring {
+            weapons: vec![10, 20, 30, 40, 50],
+            loss: 1,
+            draw: 2,
+            win: 100,
+        };
+        let weapons = ["rock", "paper", "scissors", "spock", "lizard"];
+        let game = Game::cyclic(weapons, scoring).unwrap();
+        assert_eq!(game.score(Weapon(3), Weapon(0)), 140);
+        assert_eq!(game.score(Weapon(0), Weapon(3)), 11);
+        assert_eq!(
+            Game::cyclic(weapons, Scoring::standard(3)),
+            Err("Expected scores for 5 weapons, found 3".into())
+        );
+    }
+}
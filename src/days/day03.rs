@@ -0,0 +1,471 @@
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    ops::BitAnd,
+};
+
+use advent::{Config, Input, InputError, Line, Order, ReportOptions, Reporting, Solution};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Parsed<'a> = Rucksacks<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(rucksacks: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(rucksacks))
+    }
+
+    fn part2(rucksacks: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(rucksacks))
+    }
+}
+
+impl Reporting for Day03 {
+    fn report(rucksacks: &Self::Parsed<'_>, options: &ReportOptions) -> Result<String, InputError> {
+        Ok(Report { rucksacks, options }.to_string())
+    }
+}
+
+/// Priority of an item type, from 1 for `a` to 52 for `Z`.
+fn priority(item: char) -> Option<u8> {
+    match item {
+        'a'..='z' => Some(item as u8 - b'a' + 1),
+        'A'..='Z' => Some(item as u8 - b'A' + 27),
+        _ => None,
+    }
+}
+
+fn item(priority: u8) -> char {
+    match priority {
+        1..=26 => (b'a' + priority - 1) as char,
+        _ => (b'A' + priority - 27) as char,
+    }
+}
+
+/// A set of item types, with a bit for each priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Items(u64);
+
+impl Items {
+    /// Every item type, from priority 1 to 52.
+    const ALL: Self = Self(((1 << 53) - 1) & !1);
+
+    fn of(priorities: &[u8]) -> Self {
+        Self(
+            priorities
+                .iter()
+                .fold(0, |bits, priority| bits | 1 << priority),
+        )
+    }
+
+    /// The priorities in the set, in increasing order.
+    fn iter(self) -> impl Iterator<Item = u8> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let priority = (bits != 0).then(|| bits.trailing_zeros() as u8)?;
+            bits &= bits - 1;
+            Some(priority)
+        })
+    }
+
+    fn priority_sum(self) -> usize {
+        self.iter().map(usize::from).sum()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+/// How the items of a rucksack are split into compartments, and rucksacks into groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    compartments: usize,
+    group: usize,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            compartments: 2,
+            group: 3,
+        }
+    }
+}
+
+impl Layout {
+    /// Reads `compartments` and `group` sizes, keeping the puzzle's for those not given.
+    fn parse(config: &Config) -> Result<Self, InputError> {
+        let mut layout = Self::default();
+        for entry in config.entries()? {
+            let size = match entry.key {
+                "compartments" => &mut layout.compartments,
+                "group" => &mut layout.group,
+                key => return Err(entry.error(key, format!("Unknown key {key}"))),
+            };
+            *size = entry.parse()?;
+            if *size == 0 {
+                return Err(entry.error(entry.value, format!("{} must be positive", entry.key)));
+            }
+        }
+        Ok(layout)
+    }
+}
+
+/// The priorities of the items in a rucksack, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack<'a> {
+    line: Line<'a>,
+    items: Vec<u8>,
+}
+
+impl<'a> Rucksack<'a> {
+    fn parse(line: Line<'a>, layout: Layout) -> Result<Self, InputError> {
+        let items = line
+            .chars()
+            .zip(1..)
+            .map(|(item, column)| {
+                priority(item).ok_or_else(|| {
+                    line.error(
+                        column,
+                        format!("Invalid item {item:?}, expected a-z or A-Z"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !items.len().is_multiple_of(layout.compartments) {
+            return Err(line.error(
+                1,
+                format!(
+                    "Expected a multiple of {} items, found {}",
+                    layout.compartments,
+                    items.len()
+                ),
+            ));
+        }
+        Ok(Self { line, items })
+    }
+
+    /// The items found in every compartment.
+    fn shared(&self, compartments: usize) -> Items {
+        self.items
+            .chunks(self.items.len() / compartments)
+            .map(Items::of)
+            .fold(Items::ALL, BitAnd::bitand)
+    }
+}
+
+#[derive(Debug)]
+pub struct Rucksacks<'a> {
+    layout: Layout,
+    /// One slot per rucksack line, empty where a malformed line was skipped in lenient mode, so
+    /// that skipping a line does not shift the later groups.
+    rucksacks: Vec<Option<Rucksack<'a>>>,
+}
+
+impl<'a> Rucksacks<'a> {
+    fn iter(&self) -> impl Iterator<Item = &Rucksack<'a>> {
+        self.rucksacks.iter().flatten()
+    }
+
+    /// Each complete group numbered from 1 with its badges, the items carried by all of its
+    /// rucksacks. Groups with a skipped rucksack are left out, as their badges are unknown.
+    fn badges(&self) -> impl Iterator<Item = (usize, Vec<&Rucksack<'a>>, Items)> {
+        self.rucksacks
+            .chunks_exact(self.layout.group)
+            .zip(1..)
+            .filter_map(|(group, number)| {
+                let group = group
+                    .iter()
+                    .map(Option::as_ref)
+                    .collect::<Option<Vec<_>>>()?;
+                let badges = group
+                    .iter()
+                    .map(|rucksack| Items::of(&rucksack.items))
+                    .fold(Items::ALL, BitAnd::bitand);
+                Some((number, group, badges))
+            })
+    }
+}
+
+fn part1(rucksacks: &Rucksacks) -> usize {
+    rucksacks
+        .iter()
+        .map(|rucksack| {
+            rucksack
+                .shared(rucksacks.layout.compartments)
+                .priority_sum()
+        })
+        .sum()
+}
+
+fn part2(rucksacks: &Rucksacks) -> usize {
+    rucksacks
+        .badges()
+        .map(|(_, _, badges)| badges.priority_sum())
+        .sum()
+}
+
+/// The badges of every group, with the lines of their rucksacks.
+struct Report<'a> {
+    rucksacks: &'a Rucksacks<'a>,
+    options: &'a ReportOptions,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut groups = self
+            .rucksacks
+            .badges()
+            .map(|(group, rucksacks, badges)| {
+                let first = rucksacks[0].line.number;
+                let last = rucksacks[rucksacks.len() - 1].line.number;
+                let lines = match first == last {
+                    true => first.to_string(),
+                    false => format!("{first}-{last}"),
+                };
+                let badge = match badges.iter().map(item).collect::<String>() {
+                    badge if badge.is_empty() => "-".to_owned(),
+                    badge => badge,
+                };
+                (group, lines, badge, badges.priority_sum())
+            })
+            .collect::<Vec<_>>();
+
+        groups.sort_by_key(|(_, _, _, priority)| Reverse(*priority));
+        groups.truncate(self.options.top.unwrap_or(groups.len()));
+        if self.options.order == Order::Ascending {
+            groups.reverse();
+        }
+
+        let width = groups
+            .iter()
+            .map(|(_, _, badge, _)| badge.len())
+            .chain([5])
+            .max()
+            .unwrap_or_default();
+        writeln!(f, "Group Lines       {:<width$} Priority", "Badge")?;
+        for (group, lines, badge, priority) in groups {
+            writeln!(f, "{group:<5} {lines:<11} {badge:<width$} {priority:>8}")?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Groups: {}", self.rucksacks.badges().count())?;
+        writeln!(f, "Total priority: {}", part2(self.rucksacks))
+    }
+}
+
+fn parse(input: &Input) -> Result<Rucksacks<'_>, InputError> {
+    let layout = match input.config() {
+        Some(config) => Layout::parse(config).map_err(|err| err.with_file(config.path()))?,
+        None => Layout::default(),
+    };
+
+    let diagnostics = input.diagnostics();
+    let lines = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let rucksacks = lines
+        .iter()
+        .map(|&line| diagnostics.check(Rucksack::parse(line, layout)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let incomplete = lines.len() % layout.group;
+    if incomplete > 0 {
+        let first = &lines[lines.len() - incomplete];
+        diagnostics.check::<()>(Err(first.error(
+            1,
+            format!(
+                "Incomplete group of {incomplete} rucksack(s), expected {}",
+                layout.group
+            ),
+        )))?;
+    }
+
+    Ok(Rucksacks { layout, rucksacks })
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::{Diagnostics, Mode};
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 70);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let input = INPUT.replacen('\n', "\n  \n\n", 1);
+        let input = Input::from(input.as_str());
+        let rucksacks = parse(&input).unwrap();
+        assert_eq!((part1(&rucksacks), part2(&rucksacks)), (157, 70));
+    }
+
+    #[test]
+    fn test_items() {
+        assert_eq!(priority('a'), Some(1));
+        assert_eq!(priority('z'), Some(26));
+        assert_eq!(priority('A'), Some(27));
+        assert_eq!(priority('Z'), Some(52));
+        assert!((1..=52).all(|priority| self::priority(item(priority)) == Some(priority)));
+        assert_eq!(Items::ALL.priority_sum(), (1..=52).sum::<usize>());
+        assert_eq!(Items::ALL & Items::of(&[3, 52, 3]), Items::of(&[52, 3]));
+        assert_eq!(
+            Items::of(&[52, 1, 3]).iter().collect::<Vec<_>>(),
+            vec![1, 3, 52]
+        );
+        assert_eq!((Items::of(&[1, 2]) & Items::of(&[3])).priority_sum(), 0);
+    }
+
+    #[test]
+    fn test_invalid_item() {
+        let err = parse(&Input::from("abcc\nab-c\ncé\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "Invalid item '-', expected a-z or A-Z");
+
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new("abcc\nab-c\ncé\n", diagnostics.clone());
+        let rucksacks = parse(&input).unwrap();
+        assert_eq!(
+            rucksacks.iter().map(|r| &r.items).collect::<Vec<_>>(),
+            vec![&vec![1, 2, 3, 3]]
+        );
+        assert_eq!(rucksacks.badges().count(), 0);
+        let skipped = diagnostics.skipped();
+        assert_eq!(
+            skipped
+                .iter()
+                .map(|err| (err.line, err.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 3), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_skipped_rucksack() {
+        // The second rucksack of the first group is malformed.
+        let input = INPUT.replacen("jqHRNq", "jq-RNq", 1);
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new(input.as_str(), diagnostics.clone());
+        let rucksacks = parse(&input).unwrap();
+        assert_eq!(diagnostics.skipped().len(), 1);
+        assert_eq!(part1(&rucksacks), 157 - 38);
+        assert_eq!(part2(&rucksacks), 52);
+
+        let options = ReportOptions::default();
+        let report = Report {
+            rucksacks: &rucksacks,
+            options: &options,
+        };
+        assert!(report
+            .to_string()
+            .starts_with("Group Lines       Badge Priority\n2     4-6         Z           52\n\n"));
+    }
+
+    fn with_layout(input: &str, layout: &str) -> Input {
+        Input::from(input).with_config(Some(Config::new("layout.txt", layout)))
+    }
+
+    #[test]
+    fn test_layout() {
+        let input = with_layout(INPUT, "compartments = 1\ngroup = 2\n");
+        let rucksacks = parse(&input).unwrap();
+        // A single compartment shares all of its items.
+        let total = rucksacks
+            .iter()
+            .map(|rucksack| Items::of(&rucksack.items).priority_sum())
+            .sum::<usize>();
+        assert_eq!(part1(&rucksacks), total);
+        // The pairs share FMfrs, BTqvw and GJZ.
+        assert_eq!(part2(&rucksacks), 114 + 136 + 121);
+
+        for (items, shared) in [("abcaCcAbC", 0), ("abcabc", 0), ("aabaca", 1)] {
+            let input = with_layout(items, "compartments = 3\ngroup = 1\n");
+            assert_eq!(part1(&parse(&input).unwrap()), shared);
+        }
+    }
+
+    #[test]
+    fn test_invalid_layout() {
+        let err = parse(&with_layout("abc\n", "")).unwrap_err();
+        assert_eq!(err.message, "Expected a multiple of 2 items, found 3");
+
+        let err = parse(&with_layout("abc\n", "group = 0\n")).unwrap_err();
+        assert_eq!(err.file, Some("layout.txt".into()));
+        assert_eq!((err.line, err.column), (1, 9));
+        assert_eq!(err.message, "group must be positive");
+
+        let err = parse(&with_layout("abc\n", "groups = 2\n")).unwrap_err();
+        assert_eq!(err.message, "Unknown key groups");
+    }
+
+    #[test]
+    fn test_incomplete_group() {
+        let input = INPUT.lines().take(4).collect::<Vec<_>>().join("\n");
+        let err = parse(&Input::from(input.as_str())).unwrap_err();
+        assert_eq!(err.line, 4);
+        assert_eq!(err.message, "Incomplete group of 1 rucksack(s), expected 3");
+
+        // In lenient mode the incomplete group is left out of part 2 only.
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new(input, diagnostics.clone());
+        let rucksacks = parse(&input).unwrap();
+        assert_eq!(part1(&rucksacks), 16 + 38 + 42 + 22);
+        assert_eq!(part2(&rucksacks), 18);
+        assert_eq!(diagnostics.skipped().len(), 1);
+    }
+
+    #[test]
+    fn test_report() {
+        let input = Input::from(INPUT);
+        let rucksacks = parse(&input).unwrap();
+        let options = ReportOptions::default();
+        let report = Report {
+            rucksacks: &rucksacks,
+            options: &options,
+        };
+        assert_eq!(
+            report.to_string(),
+            indoc! {"
+                Group Lines       Badge Priority
+                2     4-6         Z           52
+                1     1-3         r           18
+
+                Groups: 2
+                Total priority: 70
+            "}
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use advent::{
+    Config, Coverage, Input, InputError, Interval, IntervalSet, Line, Order, ReportOptions,
+    Reporting, Solution,
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Parsed<'a> = Assignments;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(assignments: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(count(&assignments.groups, assignments.settings.part1))
+    }
+
+    fn part2(assignments: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(count(&assignments.groups, assignments.settings.part2))
+    }
+}
+
+impl Reporting for Day04 {
+    fn report(
+        assignments: &Self::Parsed<'_>,
+        options: &ReportOptions,
+    ) -> Result<String, InputError> {
+        Ok(Report {
+            assignments,
+            options,
+        }
+        .to_string())
+    }
+}
+
+/// Which groups of assignments a part counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    /// One assignment includes all the others.
+    Contains,
+    /// Every two assignments overlap.
+    AllOverlap,
+    /// At least two assignments overlap.
+    AnyOverlap,
+}
+
+impl Criterion {
+    fn matches(self, group: &[Interval]) -> bool {
+        let starts = || group.iter().map(Interval::start);
+        let ends = || group.iter().map(Interval::end);
+        match self {
+            // Only an assignment from the earliest start to the latest end can include the others.
+            Self::Contains => {
+                let (start, end) = (starts().min(), ends().max());
+                group.iter().any(|assignment| {
+                    Some(assignment.start()) == start && Some(assignment.end()) == end
+                })
+            }
+            // Assignments overlap pairwise exactly when the latest start is before the earliest end.
+            Self::AllOverlap => starts().max() <= ends().min(),
+            // By start, an assignment overlaps an earlier one when it starts before they all end.
+            Self::AnyOverlap => {
+                let mut sorted = group.to_vec();
+                sorted.sort_unstable();
+                let mut reach = None;
+                sorted.iter().any(|assignment| {
+                    let overlaps = reach.is_some_and(|reach| assignment.start() <= reach);
+                    reach = reach.max(Some(assignment.end()));
+                    overlaps
+                })
+            }
+        }
+    }
+}
+
+impl FromStr for Criterion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "contains" => Ok(Self::Contains),
+            "all-overlap" => Ok(Self::AllOverlap),
+            "any-overlap" => Ok(Self::AnyOverlap),
+            _ => Err(format!("Unknown criterion {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    /// Number of assignments on each line.
+    group: usize,
+    /// Separates the assignments of a group. Like `range`, it may be quoted in the configuration
+    /// to hold spaces or `#`.
+    separator: String,
+    /// Separates the first and last section of an assignment.
+    range: String,
+    part1: Criterion,
+    part2: Criterion,
+    /// Sections covered by more elves than this are reported as crowded.
+    threshold: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            group: 2,
+            separator: ",".to_owned(),
+            range: "-".to_owned(),
+            part1: Criterion::Contains,
+            part2: Criterion::AnyOverlap,
+            threshold: 2,
+        }
+    }
+}
+
+impl Settings {
+    fn parse(config: &Config) -> Result<Self, InputError> {
+        let mut settings = Self::default();
+        // The last entry setting either separator, as that is where they start clashing.
+        let mut separators = None;
+        for entry in config.entries()? {
+            match entry.key {
+                "group" => {
+                    settings.group = entry.parse()?;
+                    if settings.group == 0 {
+                        return Err(entry.error(entry.value, "group must be positive"));
+                    }
+                }
+                "separator" | "range" if entry.value.is_empty() => {
+                    return Err(entry.error(entry.value, format!("{} must not be empty", entry.key)))
+                }
+                "separator" => {
+                    settings.separator = entry.value.to_owned();
+                    separators = Some(entry);
+                }
+                "range" => {
+                    settings.range = entry.value.to_owned();
+                    separators = Some(entry);
+                }
+                "part1" => settings.part1 = entry.parse()?,
+                "part2" => settings.part2 = entry.parse()?,
+                "threshold" => settings.threshold = entry.parse()?,
+                key => return Err(entry.error(key, format!("Unknown key {key}"))),
+            }
+        }
+        if let Some(entry) = separators {
+            let (separator, range) = (&settings.separator, &settings.range);
+            if separator.contains(range.as_str()) || range.contains(separator.as_str()) {
+                return Err(entry.error(
+                    entry.value,
+                    format!("separator {separator:?} clashes with range {range:?}"),
+                ));
+            }
+        }
+        Ok(settings)
+    }
+}
+
+/// The assignments on one line, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group(Vec<Interval>);
+
+impl Group {
+    fn parse(line: &Line, settings: &Settings) -> Result<Self, InputError> {
+        let assignments = line.split(settings.separator.as_str()).collect::<Vec<_>>();
+        if assignments.len() != settings.group {
+            return Err(line.error(
+                1,
+                format!(
+                    "Expected {} assignments, found {}",
+                    settings.group,
+                    assignments.len()
+                ),
+            ));
+        }
+
+        assignments
+            .into_iter()
+            .map(|assignment| {
+                Interval::parse_with(assignment, &settings.range)
+                    .map_err(|err| line.error_at(assignment, err))
+            })
+            .collect::<Result<_, _>>()
+            .map(Group)
+    }
+}
+
+#[derive(Debug)]
+pub struct Assignments {
+    settings: Settings,
+    groups: Vec<Group>,
+}
+
+impl Assignments {
+    /// The assignments of every elf, in input order.
+    fn elves(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|Group(group)| group.iter().copied())
+    }
+}
+
+fn count(groups: &[Group], criterion: Criterion) -> usize {
+    groups
+        .iter()
+        .filter(|Group(group)| criterion.matches(group))
+        .count()
+}
+
+/// How the sections are covered across all elves, with elves numbered from 1 in input order.
+/// Redundant elves are listed by the number of sections they are assigned, most first.
+struct Report<'a> {
+    assignments: &'a Assignments,
+    options: &'a ReportOptions,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let assignments = self.assignments;
+        let elves = assignments.elves().collect::<Vec<_>>();
+        let coverage = Coverage::new(assignments.elves());
+        let Some(span) = coverage.span() else {
+            return writeln!(f, "No elves");
+        };
+
+        let sections = |set: IntervalSet| match set.is_empty() {
+            true => "none".to_owned(),
+            false => format!("{set} ({} section(s))", set.len()),
+        };
+        let threshold = assignments.settings.threshold;
+        let (max, deepest) = coverage.max_depth();
+        let mut redundant = coverage.redundant();
+        redundant.sort_by_key(|&elf| Reverse(elves[elf].len()));
+        redundant.truncate(self.options.top.unwrap_or(redundant.len()));
+        if self.options.order == Order::Ascending {
+            redundant.reverse();
+        }
+        let redundant = redundant
+            .into_iter()
+            .map(|elf| format!("{} ({})", elf + 1, elves[elf]))
+            .collect::<Vec<_>>();
+
+        writeln!(f, "Elves: {}", elves.len())?;
+        writeln!(f, "Sections: {span}")?;
+        writeln!(
+            f,
+            "Uncovered: {}",
+            sections(coverage.where_depth(|depth| depth == 0))
+        )?;
+        writeln!(
+            f,
+            "Covered once: {}",
+            sections(coverage.where_depth(|depth| depth == 1))
+        )?;
+        writeln!(
+            f,
+            "Covered by more than {threshold}: {}",
+            sections(coverage.where_depth(|depth| depth > threshold))
+        )?;
+        writeln!(f, "Max depth: {max} at {deepest}")?;
+        match redundant.is_empty() {
+            true => writeln!(f, "Redundant elves: none"),
+            false => writeln!(f, "Redundant elves: {}", redundant.join(", ")),
+        }
+    }
+}
+
+fn parse(input: &Input) -> Result<Assignments, InputError> {
+    let settings = match input.config() {
+        Some(config) => Settings::parse(config).map_err(|err| err.with_file(config.path()))?,
+        None => Settings::default(),
+    };
+    let groups = input
+        .lines()
+        .parsed_with(|line| Group::parse(&line, &settings))
+        .collect::<Result<_, _>>()?;
+    Ok(Assignments { settings, groups })
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::{Diagnostics, Mode};
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        2-4,6-8
+        2-3,4-5
+        5-7,7-9
+        2-8,3-7
+        6-6,4-6
+        2-6,4-8
+    "};
+
+    #[test]
+    fn test_part1() {
+        let assignments = parse(&Input::from(INPUT)).unwrap();
+        assert_eq!(count(&assignments.groups, Criterion::Contains), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        let assignments = parse(&Input::from(INPUT)).unwrap();
+        assert_eq!(count(&assignments.groups, Criterion::AnyOverlap), 4);
+        assert_eq!(count(&assignments.groups, Criterion::AllOverlap), 4);
+    }
+
+    #[test]
+    fn test_large_sections() {
+        let input = format!("1-4000000000,3-5\n0-{max},{max}-{max}\n", max = usize::MAX);
+        let assignments = parse(&Input::from(input.as_str())).unwrap();
+        assert_eq!(count(&assignments.groups, Criterion::Contains), 2);
+        assert_eq!(count(&assignments.groups, Criterion::AnyOverlap), 2);
+        let report = Day04::report(&assignments, &ReportOptions::default()).unwrap();
+        assert!(report.contains("Max depth: 3 at 3-5\n"));
+        assert!(report.ends_with(&format!(
+            "Redundant elves: 1 (1-4000000000), 2 (3-5), 4 ({max}-{max})\n",
+            max = usize::MAX
+        )));
+    }
+
+    #[test]
+    fn test_report() {
+        let input = Input::from(INPUT).with_config(Some(Config::new("elves", "threshold = 3")));
+        let assignments = parse(&input).unwrap();
+        let report = |options| Day04::report(&assignments, &options).unwrap();
+
+        assert_eq!(
+            report(ReportOptions::default()),
+            indoc! {"
+                Elves: 12
+                Sections: 2-9
+                Uncovered: none
+                Covered once: 9-9 (1 section(s))
+                Covered by more than 3: 2-8 (7 section(s))
+                Max depth: 8 at 6-6
+                Redundant elves: 8 (3-7), 11 (2-6), 12 (4-8), 1 (2-4), 2 (6-8), 5 (5-7), 10 (4-6), 3 (2-3), 4 (4-5), 9 (6-6)
+            "}
+        );
+
+        let options = ReportOptions {
+            top: Some(2),
+            order: Order::Ascending,
+        };
+        assert!(report(options).ends_with("Redundant elves: 11 (2-6), 8 (3-7)\n"));
+    }
+
+    #[test]
+    fn test_criteria() {
+        let group = |s: &str| {
+            s.split(',')
+                .map(|assignment| assignment.parse().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let criteria = [
+            Criterion::Contains,
+            Criterion::AllOverlap,
+            Criterion::AnyOverlap,
+        ];
+        let matches = |s| criteria.map(|criterion| criterion.matches(&group(s)));
+
+        assert_eq!(matches("1-5,2-3,4-4"), [true, false, true]);
+        assert_eq!(matches("2-6,5-5,4-8"), [false, true, true]);
+        assert_eq!(matches("5-6,1-2,3-4"), [false, false, false]);
+        assert_eq!(matches("7-9,1-2,3-4,2-2"), [false, false, true]);
+        assert_eq!(matches("3-3,3-3,3-3"), [true, true, true]);
+        assert_eq!(matches("3-4"), [true, true, false]);
+        assert_eq!("all-overlap".parse(), Ok(Criterion::AllOverlap));
+        assert!("overlap".parse::<Criterion>().is_err());
+    }
+
+    #[test]
+    fn test_groups() {
+        let config = Config::new(
+            "elves",
+            indoc! {"
+                group = 3
+                separator = ;
+                range = ..
+                part1 = all-overlap
+                part2 = contains
+            "},
+        );
+        let input = Input::from("1..5; 2..3; 4..4\n2..6;5..5;4..8\n").with_config(Some(config));
+        let assignments = parse(&input).unwrap();
+        assert_eq!(assignments.elves().count(), 6);
+        assert_eq!(Day04::part1(&assignments), Ok(1));
+        assert_eq!(Day04::part2(&assignments), Ok(1));
+
+        let err = parse(&input.with_config(None)).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.message, "Expected 2 assignments, found 1");
+    }
+
+    #[test]
+    fn test_quoted_separators() {
+        let config = Config::new("elves", "separator = \" \"\nrange = \"#\" # sections\n");
+        let input = Input::from("2#4 6#8\n2#8 3#7\n").with_config(Some(config));
+        let assignments = parse(&input).unwrap();
+        assert_eq!(count(&assignments.groups, Criterion::Contains), 1);
+        assert_eq!(count(&assignments.groups, Criterion::AnyOverlap), 1);
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let error = |text| {
+            let err = Settings::parse(&Config::new("elves", text)).unwrap_err();
+            (err.line, err.column, err.message)
+        };
+        assert_eq!(
+            error("group = 0"),
+            (1, 9, "group must be positive".to_owned())
+        );
+        assert_eq!(
+            error("separator ="),
+            (1, 12, "separator must not be empty".to_owned())
+        );
+        assert_eq!(
+            error("\npart1 = some"),
+            (2, 9, "Invalid part1: Unknown criterion some".to_owned())
+        );
+        assert_eq!(error("size = 3"), (1, 1, "Unknown key size".to_owned()));
+        assert_eq!(
+            error("range = \"\""),
+            (1, 10, "range must not be empty".to_owned())
+        );
+        assert_eq!(
+            error("separator = -"),
+            (1, 13, "separator \"-\" clashes with range \"-\"".to_owned())
+        );
+        assert_eq!(
+            error("range = \"..\"\nseparator = ."),
+            (
+                2,
+                13,
+                "separator \".\" clashes with range \"..\"".to_owned()
+            )
+        );
+        assert_eq!(
+            error("separator = \" - \"\nrange = -"),
+            (
+                2,
+                9,
+                "separator \" - \" clashes with range \"-\"".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_pair() {
+        let err = parse(&Input::from("2-4,6-8\n8-2,3-4\n")).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "Reversed range \"8-2\"");
+
+        let err = parse(&Input::from("2-4,6-8\n2-4,6\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.message, "Expected start-end, found \"6\"");
+    }
+
+    #[test]
+    fn test_invalid_group() {
+        let input = "2-4,6-8\n2-4,6-8,1-1\n1-2,3-x\n";
+        let err = parse(&Input::from(input)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "Expected 2 assignments, found 3");
+
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let assignments = parse(&Input::new(input, diagnostics.clone())).unwrap();
+        assert_eq!(assignments.groups.len(), 1);
+        let skipped = diagnostics.skipped();
+        assert_eq!((skipped[1].line, skipped[1].column), (3, 5));
+        assert_eq!(
+            skipped[1].message,
+            "Invalid end \"x\": invalid digit found in string"
+        );
+    }
+}
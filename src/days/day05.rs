@@ -0,0 +1,565 @@
+use advent::{Block, Diagnostics, Input, InputError, Interval, Line, Mode, Solution};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Parsed<'a> = Procedure<'a>;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(procedure: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        part1(procedure)
+    }
+
+    fn part2(procedure: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        part2(procedure)
+    }
+}
+
+fn part1(procedure: &Procedure) -> Result<String, InputError> {
+    procedure.operate(Model::CrateMover9000)
+}
+
+fn part2(procedure: &Procedure) -> Result<String, InputError> {
+    procedure.operate(Model::CrateMover9001)
+}
+
+fn parse(input: &Input) -> Result<Procedure<'_>, InputError> {
+    let diagnostics = input.diagnostics();
+    let mut blocks = input.blocks();
+    let stacks = blocks
+        .next()
+        .map(|drawing| Stacks::parse(&drawing))
+        .transpose()?
+        .unwrap_or_default();
+    let commands = blocks
+        .flat_map(|block| {
+            block
+                .lines()
+                .parsed_with(|line| Command::parse(&line).map(|command| (line, command)))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Procedure {
+        stacks,
+        commands,
+        diagnostics,
+    })
+}
+
+#[derive(Debug)]
+pub struct Procedure<'a> {
+    stacks: Stacks,
+    commands: Vec<(Line<'a>, Command)>,
+    diagnostics: Diagnostics,
+}
+
+impl Procedure<'_> {
+    /// The crates on top of the stacks once `model` has carried out every command. In lenient
+    /// mode, commands it cannot carry out are skipped and reported.
+    fn operate(&self, model: Model) -> Result<String, InputError> {
+        let mut crane = Crane::new(model, self.stacks.clone(), self.diagnostics.mode());
+        let error = |err: &CraneError| self.commands[err.index].0.error(1, err);
+
+        crane
+            .run(self.commands.iter().map(|(_, command)| command))
+            .map_err(|err| error(&err))?;
+        for err in crane.violations() {
+            self.diagnostics.check(Err::<(), _>(error(err)))?;
+        }
+        Ok(crane.tops())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Command {
+    src: usize,
+    dst: usize,
+    quantity: usize,
+}
+
+static COMMAND_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap());
+
+impl Command {
+    fn parse(line: &Line) -> Result<Self, InputError> {
+        let caps = COMMAND_REGEX
+            .captures(line.text)
+            .ok_or_else(|| line.error(1, "Invalid command"))?;
+        let number = |group, message| {
+            let text = caps.get(group).map_or("", |m| m.as_str());
+            text.parse().map_err(|_| line.error_at(text, message))
+        };
+        Ok(Command {
+            quantity: number(1, "Invalid quantity")?,
+            src: number(2, "Invalid src")?,
+            dst: number(3, "Invalid dst")?,
+        })
+    }
+}
+
+/// How a crane moves several crates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Model {
+    /// One at a time, which reverses their order.
+    CrateMover9000,
+    /// All at once, which keeps their order.
+    CrateMover9001,
+}
+
+/// Why a command could not be carried out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The command refers to a stack that is not in the drawing.
+    UnknownStack(usize),
+    /// The source stack holds fewer crates than the command moves.
+    Underflow,
+}
+
+/// A command the crane could not carry out, with the stack heights it found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CraneError {
+    /// Position of the command in the procedure, starting at 0.
+    pub index: usize,
+    pub src: usize,
+    pub dst: usize,
+    pub quantity: usize,
+    /// Heights of the source and destination stacks, or `None` if they do not exist.
+    pub heights: (Option<usize>, Option<usize>),
+    pub violation: Violation,
+}
+
+impl Display for CraneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Command {} cannot move {} crate(s) from stack {} to stack {}: ",
+            self.index + 1,
+            self.quantity,
+            self.src,
+            self.dst
+        )?;
+        match self.violation {
+            Violation::UnknownStack(stack) => write!(f, "there is no stack {stack}"),
+            Violation::Underflow => write!(
+                f,
+                "stack {} only holds {}",
+                self.src,
+                self.heights.0.unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Carries out commands on the stacks, stopping at the first one it cannot carry out in strict
+/// mode and skipping it in lenient mode.
+struct Crane {
+    model: Model,
+    stacks: Stacks,
+    mode: Mode,
+    violations: Vec<CraneError>,
+}
+
+impl Crane {
+    fn new(model: Model, stacks: Stacks, mode: Mode) -> Self {
+        Self {
+            model,
+            stacks,
+            mode,
+            violations: Vec::new(),
+        }
+    }
+
+    fn run<'a>(
+        &mut self,
+        commands: impl IntoIterator<Item = &'a Command>,
+    ) -> Result<(), CraneError> {
+        for (index, command) in commands.into_iter().enumerate() {
+            match (self.execute(index, command), self.mode) {
+                (Ok(()), _) => {}
+                (Err(err), Mode::Strict) => return Err(err),
+                (Err(err), Mode::Lenient) => self.violations.push(err),
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, index: usize, command: &Command) -> Result<(), CraneError> {
+        let &Command { src, dst, quantity } = command;
+        let height = |stack| self.stacks.0.get(&stack).map(Vec::len);
+        let heights = (height(src), height(dst));
+        let error = |violation| CraneError {
+            index,
+            src,
+            dst,
+            quantity,
+            heights,
+            violation,
+        };
+
+        let height = match heights {
+            (None, _) => return Err(error(Violation::UnknownStack(src))),
+            (_, None) => return Err(error(Violation::UnknownStack(dst))),
+            (Some(height), _) if height < quantity => return Err(error(Violation::Underflow)),
+            (Some(height), _) => height,
+        };
+        // Moving crates onto their own stack leaves it unchanged, whatever the model.
+        if src == dst {
+            return Ok(());
+        }
+
+        let stacks = &mut self.stacks.0;
+        let mut crates = stacks
+            .get_mut(&src)
+            .map(|stack| stack.split_off(height - quantity))
+            .unwrap_or_default();
+        if self.model == Model::CrateMover9000 {
+            crates.reverse();
+        }
+        stacks.entry(dst).or_default().append(&mut crates);
+        Ok(())
+    }
+
+    fn violations(&self) -> &[CraneError] {
+        &self.violations
+    }
+
+    /// The top crates of the stacks in order of their IDs, skipping empty stacks.
+    fn tops(&self) -> String {
+        self.stacks
+            .0
+            .iter()
+            .sorted_by_key(|(id, _)| **id)
+            .filter_map(|(_, crates)| crates.last().map(String::as_str))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Stacks(HashMap<usize, Vec<String>>);
+
+impl Stacks {
+    /// Parses rows of crates such as `[A]` above a line of stack IDs. Each crate belongs to the
+    /// stack whose ID shares one of its columns, and must rest on a crate or the floor.
+    fn parse(drawing: &Block) -> Result<Self, InputError> {
+        let mut lines = drawing.lines().collect::<Vec<_>>();
+        let Some(ids) = lines.pop() else {
+            return Ok(Self::default());
+        };
+        let ids = stack_ids(&ids)?;
+        let mut stacks = vec![Vec::new(); ids.len()];
+
+        for (height, line) in lines.iter().rev().enumerate() {
+            for (span, label) in crates(line)? {
+                let column = span.start() + 1;
+                let mut below = (0..ids.len()).filter(|&stack| ids[stack].0.overlaps(&span));
+                let stack = match (below.next(), below.next()) {
+                    (Some(stack), None) => stack,
+                    (None, _) => {
+                        return Err(
+                            line.error(column, format!("Crate [{label}] is not above a stack ID"))
+                        )
+                    }
+                    (Some(first), Some(second)) => {
+                        return Err(line.error(
+                            column,
+                            format!(
+                                "Crate [{label}] is above both stacks {} and {}",
+                                ids[first].1, ids[second].1
+                            ),
+                        ))
+                    }
+                };
+
+                let id = ids[stack].1;
+                match stacks[stack].len().cmp(&height) {
+                    Ordering::Less => {
+                        return Err(line.error(
+                            column,
+                            format!("Crate [{label}] floats above an empty spot of stack {id}"),
+                        ))
+                    }
+                    Ordering::Greater => {
+                        return Err(line.error(
+                            column,
+                            format!("Crate [{label}] is beside another crate above stack {id}"),
+                        ))
+                    }
+                    Ordering::Equal => stacks[stack].push(label.to_owned()),
+                }
+            }
+        }
+
+        Ok(Stacks(
+            ids.into_iter().map(|(_, id)| id).zip(stacks).collect(),
+        ))
+    }
+}
+
+/// The whitespace-separated words of a line, with the columns they span counting from 0.
+fn words(text: &str) -> Vec<(Interval, &str)> {
+    let mut words = Vec::new();
+    let mut word = None;
+    let end = (text.len(), ' ');
+    for (column, (offset, c)) in text.char_indices().chain([end]).enumerate() {
+        match (word, c.is_whitespace()) {
+            (None, false) => word = Some((column, offset)),
+            (Some((first, start)), true) => {
+                let span = Interval::new(first, column - 1).expect("words are not empty");
+                words.push((span, &text[start..offset]));
+                word = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// The IDs of the stacks with the columns they span.
+fn stack_ids(line: &Line) -> Result<Vec<(Interval, usize)>, InputError> {
+    let mut ids: Vec<(Interval, usize)> = Vec::new();
+    for (span, word) in words(line) {
+        let column = span.start() + 1;
+        if word.starts_with('[') {
+            return Err(line.error(column, "Expected stack IDs below the crates"));
+        }
+        let id = word
+            .parse()
+            .map_err(|err| line.error(column, format!("Invalid stack ID {word:?}: {err}")))?;
+        if ids.iter().any(|&(_, other)| other == id) {
+            return Err(line.error(column, format!("Duplicate stack ID {id}")));
+        }
+        ids.push((span, id));
+    }
+    Ok(ids)
+}
+
+/// The labels of the crates drawn on a line with the columns they span, brackets included.
+fn crates<'a>(line: &Line<'a>) -> Result<Vec<(Interval, &'a str)>, InputError> {
+    let mut crates = Vec::new();
+    for (span, word) in words(line.text) {
+        // Crates may also be drawn next to each other, as in `[A][B]`.
+        let mut first = span.start();
+        let mut rest = word;
+        while !rest.is_empty() {
+            let column = first + 1;
+            let (label, after) = rest
+                .strip_prefix('[')
+                .ok_or_else(|| {
+                    line.error(
+                        column,
+                        format!("Expected a crate such as [A], found {rest:?}"),
+                    )
+                })?
+                .split_once(']')
+                .filter(|(label, _)| !label.contains('['))
+                .ok_or_else(|| line.error(column, format!("Unclosed crate {rest:?}")))?;
+            if label.is_empty() {
+                return Err(line.error(column, "Empty crate"));
+            }
+
+            let width = label.chars().count() + 2;
+            let span = Interval::new(first, first + width - 1).expect("crates are not empty");
+            crates.push((span, label));
+            first += width;
+            rest = after;
+        }
+    }
+    Ok(crates)
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::Diagnostics;
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+            [D]    
+        [N] [C]    
+        [Z] [M] [P]
+         1   2   3 
+
+        move 1 from 2 to 1
+        move 3 from 1 to 3
+        move 2 from 2 to 1
+        move 1 from 1 to 2
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()).unwrap(), "CMZ");
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn test_labels() {
+        let input = indoc! {"
+            [ab]
+            [X1] [cd] [Q]
+             1    2   10
+
+            move 1 from 10 to 2
+            move 2 from 1 to 10
+        "};
+        let input = Input::from(input);
+        let procedure = parse(&input).unwrap();
+        assert_eq!(part1(&procedure).unwrap(), "QX1");
+        assert_eq!(part2(&procedure).unwrap(), "Qab");
+    }
+
+    #[test]
+    fn test_invalid_drawing() {
+        let error = |drawing: &str| {
+            let err = parse(&Input::from(drawing)).unwrap_err();
+            (err.line, err.column, err.message)
+        };
+        let cases = [
+            ("[A] [B]\n 1\n", 1, 5, "Crate [B] is not above a stack ID"),
+            (
+                "    [A]\n[B]\n 1   2\n",
+                1,
+                5,
+                "Crate [A] floats above an empty spot of stack 2",
+            ),
+            (
+                "[A]\n[B]x\n 1\n",
+                2,
+                4,
+                "Expected a crate such as [A], found \"x\"",
+            ),
+            ("[AB\n 1\n", 1, 1, "Unclosed crate \"[AB\""),
+            ("[]\n 1\n", 1, 1, "Empty crate"),
+            (
+                "[ABCD]\n 1  2\n",
+                1,
+                1,
+                "Crate [ABCD] is above both stacks 1 and 2",
+            ),
+            (
+                "[A][B]\n  10\n",
+                1,
+                4,
+                "Crate [B] is beside another crate above stack 10",
+            ),
+            (
+                "[A]\n 1 x\n",
+                2,
+                4,
+                "Invalid stack ID \"x\": invalid digit found in string",
+            ),
+            ("[A]\n 1  1\n", 2, 5, "Duplicate stack ID 1"),
+            ("[A]\n[B]\n", 2, 1, "Expected stack IDs below the crates"),
+        ];
+        for (drawing, line, column, message) in cases {
+            assert_eq!(
+                error(drawing),
+                (line, column, message.to_owned()),
+                "{drawing:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_crane() {
+        let input = Input::from(INPUT);
+        let procedure = parse(&input).unwrap();
+        let commands = [
+            Command {
+                src: 2,
+                dst: 2,
+                quantity: 3,
+            },
+            Command {
+                src: 2,
+                dst: 4,
+                quantity: 1,
+            },
+            Command {
+                src: 3,
+                dst: 1,
+                quantity: 2,
+            },
+            Command {
+                src: 1,
+                dst: 3,
+                quantity: 2,
+            },
+        ];
+        let run = |model, mode| {
+            let mut crane = Crane::new(model, procedure.stacks.clone(), mode);
+            crane
+                .run(&commands)
+                .map(|()| (crane.tops(), crane.violations().to_vec()))
+        };
+
+        assert_eq!(
+            run(Model::CrateMover9000, Mode::Strict),
+            Err(CraneError {
+                index: 1,
+                src: 2,
+                dst: 4,
+                quantity: 1,
+                heights: (Some(3), None),
+                violation: Violation::UnknownStack(4),
+            })
+        );
+
+        let (tops, violations) = run(Model::CrateMover9000, Mode::Lenient).unwrap();
+        assert_eq!(tops, "DZ");
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[1].index, 2);
+        assert_eq!(violations[1].heights, (Some(1), Some(2)));
+        assert_eq!(violations[1].violation, Violation::Underflow);
+        assert_eq!(
+            violations[1].to_string(),
+            "Command 3 cannot move 2 crate(s) from stack 3 to stack 1: stack 3 only holds 1"
+        );
+
+        let (tops, _) = run(Model::CrateMover9001, Mode::Lenient).unwrap();
+        assert_eq!(tops, "DN");
+    }
+
+    #[test]
+    fn test_invalid_command() {
+        let input = format!("{INPUT}move 4 from 1 to 2\nmove 1 from 3 to 9\n");
+        let err = part1(&parse(&Input::from(input.as_str())).unwrap()).unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (10, "move 4 from 1 to 2"));
+        assert_eq!(
+            err.message,
+            "Command 5 cannot move 4 crate(s) from stack 1 to stack 2: stack 1 only holds 1"
+        );
+
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new(input.as_str(), diagnostics.clone());
+        let procedure = parse(&input).unwrap();
+        assert_eq!(part2(&procedure).unwrap(), "MCD");
+        let skipped = diagnostics.skipped();
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[1].line, 11);
+        assert!(skipped[1].message.ends_with("there is no stack 9"));
+
+        let input = format!("{INPUT}move 1 from 99999999999999999999 to 2\n");
+        let err = parse(&Input::from(input.as_str())).unwrap_err();
+        assert_eq!((err.line, err.column), (10, 13));
+        assert_eq!(err.message, "Invalid src");
+    }
+}
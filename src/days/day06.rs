@@ -0,0 +1,148 @@
+use std::io;
+
+use advent::{
+    marker::{self, MarkerDetector},
+    Bytes, Error, Input, InputError, Solution, Streaming,
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Parsed<'a> = &'a [u8];
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        Ok(input.as_bytes())
+    }
+
+    fn part1(datastream: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(datastream))
+    }
+
+    fn part2(datastream: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(datastream))
+    }
+}
+
+impl Streaming for Day06 {
+    fn stream(input: &mut Bytes, report: &mut dyn FnMut(u8, String)) -> Result<(), Error> {
+        Ok(stream(input, |part, marker| {
+            report(part, marker.to_string())
+        })?)
+    }
+}
+
+fn part1(datastream: &[u8]) -> usize {
+    marker::first_marker(datastream.iter().copied(), 4).unwrap_or_default()
+}
+
+fn part2(datastream: &[u8]) -> usize {
+    marker::first_marker(datastream.iter().copied(), 14).unwrap_or_default()
+}
+
+/// Scans both markers in a single pass, reporting each as soon as it ends.
+fn stream(
+    bytes: impl Iterator<Item = io::Result<u8>>,
+    mut report: impl FnMut(u8, usize),
+) -> io::Result<()> {
+    let mut pending = vec![(1, MarkerDetector::new(4)), (2, MarkerDetector::new(14))];
+
+    for byte in bytes {
+        let byte = byte?;
+        pending.retain_mut(|(part, detector)| {
+            let found = detector.push(byte);
+            if found {
+                report(*part, detector.position());
+            }
+            !found
+        });
+        if pending.is_empty() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Read};
+
+    use super::*;
+
+    /// A reader returning its data a few bytes at a time, in irregular amounts.
+    struct Trickle<I> {
+        data: I,
+        sizes: std::iter::Cycle<std::array::IntoIter<usize, 5>>,
+    }
+
+    impl<I: Iterator<Item = u8>> Trickle<I> {
+        fn new(data: I) -> Self {
+            Self {
+                data,
+                sizes: [1, 3, 2, 7, 1].into_iter().cycle(),
+            }
+        }
+    }
+
+    impl<I: Iterator<Item = u8>> Read for Trickle<I> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let size = self.sizes.next().unwrap().min(buf.len());
+            let mut read = 0;
+            for (slot, byte) in buf[..size].iter_mut().zip(self.data.by_ref()) {
+                *slot = byte;
+                read += 1;
+            }
+            Ok(read)
+        }
+    }
+
+    fn streamed(reader: impl Read + 'static) -> (Vec<(u8, usize)>, u64) {
+        let mut bytes = Bytes::new(BufReader::with_capacity(4, reader));
+        let mut answers = Vec::new();
+        stream(&mut bytes, |part, marker| answers.push((part, marker))).unwrap();
+        (answers, bytes.position())
+    }
+
+    #[test]
+    fn test_stream() {
+        let data = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_vec();
+        let (answers, read) = streamed(Trickle::new(data.into_iter()));
+        assert_eq!(answers, vec![(1, 7), (2, 19)]);
+        assert_eq!(read, 19);
+
+        let (answers, _) = streamed(Trickle::new(b"abcdd".to_vec().into_iter()));
+        assert_eq!(answers, vec![(1, 4)]);
+    }
+
+    #[test]
+    fn test_stream_unbounded() {
+        // The markers follow a long run without one, and the input never ends after them.
+        let data = std::iter::repeat_n(b'a', 100_000)
+            .chain(*b"abcdefghijklmn")
+            .chain(std::iter::repeat(b'z'));
+        let (answers, read) = streamed(Trickle::new(data));
+        assert_eq!(answers, vec![(1, 100_004), (2, 100_014)]);
+        assert_eq!(read, 100_014);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
+        assert_eq!(part1(b"nppdvjthqldpwncqszvftbrmjlhg"), 6);
+        assert_eq!(part1(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
+        assert_eq!(part1(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
+        assert_eq!(part2(b"bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
+        assert_eq!(part2(b"nppdvjthqldpwncqszvftbrmjlhg"), 23);
+        assert_eq!(part2(b"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
+        assert_eq!(part2(b"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    iter::FromIterator,
+    path::{Path, PathBuf},
+};
+
+use advent::{Input, InputError, Line, Solution};
+use itertools::Itertools;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Parsed<'a> = FileSystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(fs: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(fs))
+    }
+
+    fn part2(fs: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(fs))
+    }
+}
+
+fn part1(fs: &FileSystem) -> usize {
+    fs.dirs()
+        .filter(|(_, size)| *size <= 100000)
+        .map(|(_, size)| size)
+        .sum()
+}
+
+fn part2(fs: &FileSystem) -> usize {
+    let used = fs.dir_size("/");
+    let free = 70_000_000 - used;
+
+    fs.dirs()
+        .map(|(_, size)| size)
+        .sorted()
+        .find(|size| *size >= 30_000_000 - free)
+        .unwrap_or_default()
+}
+
+fn parse(input: &Input) -> Result<FileSystem, InputError> {
+    input
+        .lines()
+        .parsed_with(|line| Entry::parse(&line))
+        .collect()
+}
+
+pub struct FileSystem {
+    cwd: PathBuf,
+    dirs: HashMap<PathBuf, usize>,
+}
+
+impl FileSystem {
+    fn new() -> Self {
+        Self {
+            cwd: PathBuf::from("/"),
+            dirs: HashMap::new(),
+        }
+    }
+
+    fn cd(&mut self, dir: &str) {
+        if dir == ".." {
+            self.cwd.pop();
+        } else {
+            self.cwd.push(dir)
+        }
+    }
+
+    fn add_size(&mut self, size: usize) {
+        for dir in self.cwd.ancestors() {
+            *self.dirs.entry(dir.to_owned()).or_default() += size;
+        }
+    }
+
+    fn dir_size(&self, dir: impl AsRef<Path>) -> usize {
+        self.dirs.get(dir.as_ref()).copied().unwrap_or_default()
+    }
+
+    fn dirs(&self) -> impl Iterator<Item = (&Path, usize)> {
+        self.dirs.iter().map(|(name, size)| (name.as_path(), *size))
+    }
+}
+
+impl FromIterator<Entry> for FileSystem {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        iter.into_iter()
+            .fold(FileSystem::new(), |mut fs, entry| match entry {
+                Entry::Cd(dir) => {
+                    fs.cd(&dir);
+                    fs
+                }
+                Entry::File(size) => {
+                    fs.add_size(size);
+                    fs
+                }
+                Entry::Ls | Entry::Dir => fs,
+            })
+    }
+}
+
+/// A line of the terminal output, keeping only what the sizes depend on.
+#[derive(Debug)]
+enum Entry {
+    Ls,
+    Cd(String),
+    Dir,
+    File(usize),
+}
+
+impl Entry {
+    fn parse(line: &Line) -> Result<Self, InputError> {
+        match line.split(' ').collect::<Vec<_>>().as_slice() {
+            ["$", "cd", dir] => Ok(Entry::Cd(dir.to_string())),
+            ["$", "ls"] => Ok(Entry::Ls),
+            ["$", "cd"] => Err(line.error_at(&line.text[line.text.len()..], "Missing directory")),
+            ["$", "cd" | "ls", ..] => Err(line.error(1, "Invalid entry")),
+            ["$", command, ..] => Err(line.error_at(command, "Invalid command")),
+            ["dir", _] => Ok(Entry::Dir),
+            [size, _] => Ok(Entry::File(size.parse::<usize>().map_err(|err| {
+                line.error_at(size, format!("Invalid file size: {err}"))
+            })?)),
+            _ => Err(line.error(1, "Invalid entry")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        $ cd /
+        $ ls
+        dir a
+        14848514 b.txt
+        8504156 c.dat
+        dir d
+        $ cd a
+        $ ls
+        dir e
+        29116 f
+        2557 g
+        62596 h.lst
+        $ cd e
+        $ ls
+        584 i
+        $ cd ..
+        $ cd ..
+        $ cd d
+        $ ls
+        4060174 j
+        8033020 d.log
+        5626152 d.ext
+        7214296 k
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn test_invalid_entry() {
+        let err = parse(&Input::from("$ cd /\n$ ls\n12x b.txt\n"))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(
+            err.message,
+            "Invalid file size: invalid digit found in string"
+        );
+
+        let err = parse(&Input::from("$ cd /\n$ ls -l\n")).err().unwrap();
+        assert_eq!((err.line, err.message.as_str()), (2, "Invalid entry"));
+
+        let err = parse(&Input::from("$ cd /\n$ cd\n")).err().unwrap();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.message, "Missing directory");
+
+        let err = parse(&Input::from("$ cd /\n$ pwd\n")).err().unwrap();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "Invalid command");
+    }
+}
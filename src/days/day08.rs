@@ -0,0 +1,80 @@
+use advent::{iter::IteratorExt, Direction, Grid, Input, InputError, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Parsed<'a> = Grid<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(grid))
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(grid))
+    }
+}
+
+fn part1(grid: &Grid<u32>) -> usize {
+    grid.iter()
+        .filter(|(pos, tree)| {
+            Direction::ORTHOGONAL
+                .into_iter()
+                .any(|direction| grid.ray(*pos, direction).all(|(_, other)| other < tree))
+        })
+        .count()
+}
+
+fn part2(grid: &Grid<u32>) -> usize {
+    grid.iter()
+        .map(|(pos, tree)| {
+            Direction::ORTHOGONAL
+                .into_iter()
+                .map(|direction| {
+                    grid.ray(pos, direction)
+                        .take_while_inclusive(|(_, other)| *other < tree)
+                        .count()
+                })
+                .product()
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+fn parse(input: &Input) -> Result<Grid<u32>, InputError> {
+    Grid::parse(input.lines(), |c| {
+        c.to_digit(10).ok_or("Invalid tree height")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        30373
+        25512
+        65332
+        33549
+        35390
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 21);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 8);
+    }
+}
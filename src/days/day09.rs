@@ -1,21 +1,36 @@
 use std::{
     collections::HashSet,
     ops::{Add, Sub},
-    str::FromStr,
 };
 
-use advent::input_lines;
+use advent::{Input, InputError, Line, Solution};
 
-fn main() -> std::io::Result<()> {
-    println!("Part1: {}", part1(input_lines("inputs/day-09.txt")?));
-    println!("Part2: {}", part2(input_lines("inputs/day-09.txt")?));
-    Ok(())
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Parsed<'a> = Vec<Movement>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(movements: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(movements))
+    }
+
+    fn part2(movements: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(movements))
+    }
 }
 
-fn part1(input: impl Iterator<Item = String>) -> usize {
+fn part1(movements: &[Movement]) -> usize {
     let mut rope = Rope::default();
-    input
-        .filter_map(|line| line.parse::<Movement>().ok())
+    movements
+        .iter()
         .flat_map(|movement| movement.into_iter())
         .map(|position| {
             rope.move_head(position);
@@ -25,10 +40,10 @@ fn part1(input: impl Iterator<Item = String>) -> usize {
         .len()
 }
 
-fn part2(input: impl Iterator<Item = String>) -> usize {
+fn part2(movements: &[Movement]) -> usize {
     let mut ropes = [Rope::default(); 9];
-    input
-        .filter_map(|line| line.parse::<Movement>().ok())
+    movements
+        .iter()
         .flat_map(|movement| movement.into_iter())
         .filter_map(|position| {
             ropes
@@ -46,8 +61,15 @@ fn part2(input: impl Iterator<Item = String>) -> usize {
         .len()
 }
 
+fn parse(input: &Input) -> Result<Vec<Movement>, InputError> {
+    input
+        .lines()
+        .parsed_with(|line| Movement::parse(&line))
+        .collect()
+}
+
 #[derive(Debug, Default, Clone, Copy, Hash, Eq, PartialEq)]
-struct Vec2 {
+pub struct Vec2 {
     x: isize,
     y: isize,
 }
@@ -110,6 +132,7 @@ impl Rope {
     }
 }
 
+#[derive(Clone, Copy)]
 enum Dir {
     Up,
     Down,
@@ -117,20 +140,23 @@ enum Dir {
     Right,
 }
 
-struct Movement(Dir, usize);
-
-impl FromStr for Movement {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (dir, steps) = s.split_once(' ').ok_or("Invalid movement")?;
-        let steps = steps.parse::<usize>().map_err(|_| "Invalid steps")?;
+#[derive(Clone, Copy)]
+pub struct Movement(Dir, usize);
+
+impl Movement {
+    fn parse(line: &Line) -> Result<Self, InputError> {
+        let (dir, steps) = line
+            .split_once(' ')
+            .ok_or_else(|| line.error(1, "Invalid movement"))?;
+        let steps = steps
+            .parse::<usize>()
+            .map_err(|_| line.error_at(steps, "Invalid steps"))?;
         match dir {
             "U" => Ok(Movement(Dir::Up, steps)),
             "D" => Ok(Movement(Dir::Down, steps)),
             "L" => Ok(Movement(Dir::Left, steps)),
             "R" => Ok(Movement(Dir::Right, steps)),
-            _ => Err("Invalid direction"),
+            _ => Err(line.error_at(dir, "Invalid direction")),
         }
     }
 }
@@ -152,7 +178,6 @@ impl IntoIterator for Movement {
 
 #[cfg(test)]
 mod tests {
-    use advent::static_input_lines;
     use indoc::indoc;
 
     use super::*;
@@ -169,7 +194,7 @@ mod tests {
             L 5
             R 2
         "};
-        assert_eq!(part1(static_input_lines(input)), 13);
+        assert_eq!(part1(&parse(&Input::from(input)).unwrap()), 13);
     }
 
     #[test]
@@ -184,6 +209,22 @@ mod tests {
             L 25
             U 20
         "};
-        assert_eq!(part2(static_input_lines(input)), 36);
+        assert_eq!(part2(&parse(&Input::from(input)).unwrap()), 36);
+    }
+
+    #[test]
+    fn test_invalid_movement() {
+        let input = indoc! {"
+            R 4
+            U four
+            L 3
+        "};
+        let err = parse(&Input::from(input)).err().unwrap();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "U four"));
+        assert_eq!(err.message, "Invalid steps");
+
+        let err = parse(&Input::from("R 4\nL 3\nX 1\n")).err().unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.message, "Invalid direction");
     }
 }
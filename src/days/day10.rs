@@ -1,16 +1,32 @@
-use std::{iter, str::FromStr};
+use std::iter;
 
-use advent::input_lines;
+use advent::{Input, InputError, Line, Solution};
 use itertools::Itertools;
 
-fn main() -> std::io::Result<()> {
-    println!("Part1: {}", part1(input_lines("inputs/day-10.txt")?));
-    println!("Part2:\n{}", part2(input_lines("inputs/day-10.txt")?));
-    Ok(())
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed<'a> = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = String;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(instructions: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(instructions))
+    }
+
+    fn part2(instructions: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(instructions))
+    }
 }
 
-fn part1(input: impl Iterator<Item = String>) -> i64 {
-    cycles(input)
+fn part1(instructions: &[Instruction]) -> i64 {
+    cycles(instructions)
         .filter_map(|(cycle, x)| {
             let cycle = cycle + 1;
             [20, 60, 100, 140, 180, 220]
@@ -20,8 +36,8 @@ fn part1(input: impl Iterator<Item = String>) -> i64 {
         .sum()
 }
 
-fn part2(input: impl Iterator<Item = String>) -> String {
-    cycles(input)
+fn part2(instructions: &[Instruction]) -> String {
+    cycles(instructions)
         .chunks(40)
         .into_iter()
         .map(|lines| {
@@ -36,11 +52,12 @@ fn part2(input: impl Iterator<Item = String>) -> String {
         .join("\n")
 }
 
-fn cycles(input: impl Iterator<Item = String>) -> impl Iterator<Item = (i64, i64)> {
+fn cycles(instructions: &[Instruction]) -> impl Iterator<Item = (i64, i64)> + '_ {
     iter::once(0)
         .chain(
-            input
-                .filter_map(|line| line.parse::<Instruction>().ok())
+            instructions
+                .iter()
+                .copied()
                 .flat_map(|instruction| match instruction {
                     Instruction::Noop => vec![0],
                     Instruction::Addx(x) => vec![0, x],
@@ -54,34 +71,45 @@ fn cycles(input: impl Iterator<Item = String>) -> impl Iterator<Item = (i64, i64
         .map(|(cycle, x)| (cycle as i64, x))
 }
 
+fn parse(input: &Input) -> Result<Vec<Instruction>, InputError> {
+    input
+        .lines()
+        .parsed_with(|line| Instruction::parse(&line))
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     Addx(i64),
     Noop,
 }
 
-impl FromStr for Instruction {
-    type Err = &'static str;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_at(4) {
-            ("noop", _) => Ok(Instruction::Noop),
-            ("addx", n) => Ok(Instruction::Addx(
-                n.trim().parse().map_err(|_| "Invalid number")?,
-            )),
-            _ => Err("Invalid instruction"),
+impl Instruction {
+    fn parse(line: &Line) -> Result<Self, InputError> {
+        // Without an operand, it is the empty text at the end of the line.
+        match line
+            .split_once(' ')
+            .unwrap_or((line.text, &line.text[line.text.len()..]))
+        {
+            ("noop", "") => Ok(Instruction::Noop),
+            ("addx", n) => {
+                let n = n.trim();
+                Ok(Instruction::Addx(
+                    n.parse().map_err(|_| line.error_at(n, "Invalid number"))?,
+                ))
+            }
+            _ => Err(line.error(1, "Invalid instruction")),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use advent::static_input_lines;
     use indoc::indoc;
 
     use super::*;
 
-    const INPUT: &'static str = indoc! {"
+    const INPUT: &str = indoc! {"
         addx 15
         addx -11
         addx 6
@@ -232,7 +260,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(static_input_lines(INPUT)), 13140);
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 13140);
     }
 
     #[test]
@@ -245,6 +273,21 @@ mod tests {
             ######......######......######......####
             #######.......#######.......#######.....
         "};
-        assert_eq!(part2(static_input_lines(INPUT)), output.trim());
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), output.trim());
+    }
+
+    #[test]
+    fn test_invalid_instruction() {
+        let err = parse(&Input::from("noop\naddx 1x\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.message, "Invalid number");
+
+        let err = parse(&Input::from("noop\naddx\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.message, "Invalid number");
+
+        let err = parse(&Input::from("noop\nadd 1\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "Invalid instruction");
     }
 }
@@ -0,0 +1,402 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
+use advent::{Block, Input, InputError, Solution};
+use itertools::Itertools;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed<'a> = HashMap<u32, Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError> {
+        parse(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError> {
+        Ok(part1(monkeys))
+    }
+
+    fn part2(monkeys: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError> {
+        Ok(part2(monkeys))
+    }
+}
+
+fn part1(monkeys: &HashMap<u32, Monkey>) -> usize {
+    rounds(monkeys.clone(), 20, 3)
+}
+
+fn part2(monkeys: &HashMap<u32, Monkey>) -> usize {
+    rounds(monkeys.clone(), 10_000, 1)
+}
+
+fn parse(input: &Input) -> Result<HashMap<u32, Monkey>, InputError> {
+    let monkeys = input
+        .blocks()
+        .map(|block| {
+            Ok(block
+                .parse_with(Monkey::parse)?
+                .map(|monkey| (block, monkey)))
+        })
+        .flatten_ok()
+        .collect::<Result<Vec<_>, InputError>>()?;
+
+    let mut ids = HashMap::new();
+    for (block, monkey) in &monkeys {
+        if let Some(previous) = ids.insert(monkey.id, block.number) {
+            return Err(block.error(format!(
+                "Duplicate monkey {}, already defined on line {previous}",
+                monkey.id
+            )));
+        }
+    }
+    // Also catches throws to a monkey whose block was skipped in lenient mode.
+    for (block, monkey) in &monkeys {
+        let (target1, target2) = monkey.targets;
+        if let Some(target) = [target1, target2]
+            .into_iter()
+            .find(|target| !ids.contains_key(target))
+        {
+            return Err(block.error(format!(
+                "Monkey {} throws to unknown monkey {target}",
+                monkey.id
+            )));
+        }
+    }
+
+    Ok(monkeys
+        .into_iter()
+        .map(|(_, monkey)| (monkey.id, monkey))
+        .collect())
+}
+
+fn rounds(monkeys: HashMap<u32, Monkey>, rounds: usize, divisor: u128) -> usize {
+    let keys = monkeys.keys().sorted().copied().collect::<Vec<_>>();
+
+    let common_multiple = monkeys
+        .values()
+        .map(|monkey| monkey.divisor)
+        .product::<u128>();
+
+    (0..rounds)
+        .flat_map(|_| keys.iter())
+        .fold(monkeys, |mut monkeys, i| {
+            let monkey = monkeys.get_mut(i).unwrap();
+
+            let moves = monkey
+                .items
+                .drain(..)
+                .rev()
+                .map(|item| {
+                    let item = item % common_multiple;
+                    let worry = (monkey.operation)(item) / divisor;
+                    let target = if worry.is_multiple_of(monkey.divisor) {
+                        monkey.targets.0
+                    } else {
+                        monkey.targets.1
+                    };
+                    (target, worry)
+                })
+                .collect::<Vec<_>>();
+
+            monkey.count += moves.len();
+
+            for (target, worry) in moves {
+                monkeys.get_mut(&target).unwrap().items.push_back(worry);
+            }
+
+            monkeys
+        })
+        .into_values()
+        .map(|monkey| monkey.count)
+        .sorted_by_key(|count| Reverse(*count))
+        .take(2)
+        .product()
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    id: u32,
+    items: VecDeque<u128>,
+    operation: Rc<dyn Fn(u128) -> u128>,
+    divisor: u128,
+    targets: (u32, u32),
+    count: usize,
+}
+
+impl Monkey {
+    fn parse(block: &Block) -> Result<Self, InputError> {
+        use nom::{
+            branch::alt,
+            bytes::complete::tag,
+            character::complete::{line_ending, multispace0, space0, u128, u32},
+            combinator::{cut, eof, map},
+            error::{context, VerboseError, VerboseErrorKind},
+            multi::separated_list1,
+            sequence::{delimited, preceded, terminated},
+            Finish, IResult,
+        };
+
+        type Result<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+        /// Parses one indented line of the block with `parser`.
+        fn line<'a, T>(
+            parser: impl FnMut(&'a str) -> Result<'a, T>,
+        ) -> impl FnMut(&'a str) -> Result<'a, T> {
+            delimited(
+                space0,
+                parser,
+                context(
+                    "Unexpected text at the end of the line",
+                    preceded(space0, alt((line_ending, eof))),
+                ),
+            )
+        }
+
+        fn parse_id(input: &str) -> Result<'_, u32> {
+            delimited(
+                context("Expected `Monkey`", tag("Monkey ")),
+                context("Invalid monkey id", u32),
+                context("Expected `:`", tag(":")),
+            )(input)
+        }
+
+        fn parse_items(input: &str) -> Result<'_, Vec<u128>> {
+            preceded(
+                context("Expected `Starting items:`", tag("Starting items: ")),
+                separated_list1(tag(", "), cut(context("Invalid item", u128))),
+            )(input)
+        }
+
+        fn parse_operation(input: &str) -> Result<'_, Rc<dyn Fn(u128) -> u128>> {
+            enum Expr {
+                Old,
+                Const(u128),
+                Add(Box<Expr>, Box<Expr>),
+                Mul(Box<Expr>, Box<Expr>),
+            }
+
+            impl Expr {
+                fn eval(&self, old: u128) -> u128 {
+                    match self {
+                        Expr::Old => old,
+                        Expr::Const(n) => *n,
+                        Expr::Add(lhs, rhs) => lhs.eval(old) + rhs.eval(old),
+                        Expr::Mul(lhs, rhs) => lhs.eval(old) * rhs.eval(old),
+                    }
+                }
+            }
+
+            fn parse_operand(input: &str) -> Result<'_, Expr> {
+                context(
+                    "Expected `old` or a number",
+                    alt((map(tag("old"), |_| Expr::Old), map(u128, Expr::Const))),
+                )(input)
+            }
+
+            let (i, _) = context("Expected `Operation:`", tag("Operation: "))(input)?;
+            let (i, _) = context("Expected `new =`", tag("new = "))(i)?;
+            let (i, lhs) = parse_operand(i)?;
+            let (i, op) = delimited(
+                space0,
+                context("Expected `+` or `*`", alt((tag("+"), tag("*")))),
+                space0,
+            )(i)?;
+            let (i, rhs) = parse_operand(i)?;
+
+            let expr = match op {
+                "+" => Expr::Add(Box::new(lhs), Box::new(rhs)),
+                "*" => Expr::Mul(Box::new(lhs), Box::new(rhs)),
+                _ => unreachable!(),
+            };
+
+            Ok((i, Rc::new(move |old| expr.eval(old))))
+        }
+
+        fn parse_test(input: &str) -> Result<'_, u128> {
+            preceded(
+                context("Expected `Test: divisible by`", tag("Test: divisible by ")),
+                context("Invalid divisor", u128),
+            )(input)
+        }
+
+        fn parse_throw(input: &str) -> Result<'_, u32> {
+            preceded(
+                context("Expected `throw to monkey`", tag("throw to monkey ")),
+                context("Invalid monkey id", u32),
+            )(input)
+        }
+
+        fn parse_monkey(input: &str) -> Result<'_, Monkey> {
+            let (i, id) = line(parse_id)(input)?;
+            let (i, items) = line(parse_items)(i)?;
+            let (i, operation) = line(parse_operation)(i)?;
+            let (i, test) = line(parse_test)(i)?;
+            let (i, target1) = line(preceded(
+                context("Expected `If true:`", tag("If true: ")),
+                parse_throw,
+            ))(i)?;
+            let (i, target2) = line(preceded(
+                context("Expected `If false:`", tag("If false: ")),
+                parse_throw,
+            ))(i)?;
+
+            Ok((
+                i,
+                Monkey {
+                    id,
+                    items: items.into(),
+                    operation,
+                    divisor: test,
+                    targets: (target1, target2),
+                    count: 0,
+                },
+            ))
+        }
+
+        let parsed = terminated(
+            parse_monkey,
+            context(
+                "Unexpected text after the monkey",
+                preceded(multispace0, eof),
+            ),
+        )(block.text);
+        match parsed.finish() {
+            Ok((_, monkey)) => Ok(monkey),
+            // Parsing stopped where the innermost error is, and the innermost context describes
+            // what was expected there.
+            Err(err) => {
+                let input = err.errors.first().map_or(block.text, |(input, _)| input);
+                let message = err
+                    .errors
+                    .iter()
+                    .find_map(|(_, kind)| match kind {
+                        VerboseErrorKind::Context(message) => Some(*message),
+                        _ => None,
+                    })
+                    .unwrap_or("Invalid monkey");
+                Err(block.error_at(input, message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent::{Diagnostics, Mode};
+    use indoc::indoc;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"
+        Monkey 0:
+          Starting items: 79, 98
+          Operation: new = old * 19
+          Test: divisible by 23
+            If true: throw to monkey 2
+            If false: throw to monkey 3
+
+        Monkey 1:
+          Starting items: 54, 65, 75, 74
+          Operation: new = old + 6
+          Test: divisible by 19
+            If true: throw to monkey 2
+            If false: throw to monkey 0
+
+        Monkey 2:
+          Starting items: 79, 60, 97
+          Operation: new = old * old
+          Test: divisible by 13
+            If true: throw to monkey 1
+            If false: throw to monkey 3
+
+        Monkey 3:
+          Starting items: 74
+          Operation: new = old + 3
+          Test: divisible by 17
+            If true: throw to monkey 0
+            If false: throw to monkey 1
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse(&Input::from(INPUT)).unwrap()), 10605);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse(&Input::from(INPUT)).unwrap()), 2713310158);
+    }
+
+    #[test]
+    fn test_unknown_target() {
+        let input = INPUT.replace("throw to monkey 0", "throw to monkey 7");
+        let err = parse(&Input::from(input.as_str())).err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (8, "Monkey 1:"));
+        assert_eq!(err.message, "Monkey 1 throws to unknown monkey 7 (block 1)");
+
+        let input = INPUT.replace("Starting items: 74\n", "Starting items: x\n");
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let err = parse(&Input::new(input.as_str(), diagnostics.clone()))
+            .err()
+            .unwrap();
+        assert_eq!(diagnostics.skipped().len(), 1);
+        assert_eq!(err.line, 1);
+        assert_eq!(err.message, "Monkey 0 throws to unknown monkey 3 (block 0)");
+    }
+
+    #[test]
+    fn test_invalid_monkey() {
+        let error = |input: String| {
+            let err = parse(&Input::from(input.as_str())).err().unwrap();
+            (err.line, err.column, err.message)
+        };
+
+        assert_eq!(
+            error(INPUT.replace("Operation: new = old + 6", "Operation new = old + 6")),
+            (10, 3, "Expected `Operation:` (block 1)".to_owned())
+        );
+        assert_eq!(
+            error(INPUT.replace("79, 60, 97", "79, x, 97")),
+            (16, 23, "Invalid item (block 2)".to_owned())
+        );
+        assert_eq!(
+            error(INPUT.replace("old * old", "old / old")),
+            (17, 24, "Expected `+` or `*` (block 2)".to_owned())
+        );
+        assert_eq!(
+            error(INPUT.replace("by 17", "by 17 or 3")),
+            (
+                25,
+                25,
+                "Unexpected text at the end of the line (block 3)".to_owned()
+            )
+        );
+        assert_eq!(
+            error(INPUT.replace("monkey 0\n\nMonkey 2", "monkey 0\n  Done\n\nMonkey 2")),
+            (
+                14,
+                3,
+                "Unexpected text after the monkey (block 1)".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_duplicate_monkey() {
+        let input = INPUT.replace("Monkey 2:", "Monkey 1:");
+        let err = parse(&Input::from(input.as_str())).err().unwrap();
+        assert_eq!((err.line, err.text.as_str()), (15, "Monkey 1:"));
+        assert_eq!(
+            err.message,
+            "Duplicate monkey 1, already defined on line 8 (block 2)"
+        );
+    }
+}
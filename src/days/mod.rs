@@ -0,0 +1,27 @@
+use advent::Day;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+
+pub const DAYS: &[Day] = &[
+    Day::reporting::<day01::Day01>(),
+    Day::reporting::<day02::Day02>().configurable(),
+    Day::reporting::<day03::Day03>().configurable(),
+    Day::reporting::<day04::Day04>().configurable(),
+    Day::of::<day05::Day05>(),
+    Day::streaming::<day06::Day06>(),
+    Day::of::<day07::Day07>(),
+    Day::of::<day08::Day08>(),
+    Day::of::<day09::Day09>(),
+    Day::of::<day10::Day10>(),
+    Day::of::<day11::Day11>(),
+];
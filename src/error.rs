@@ -0,0 +1,188 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+};
+
+use crate::Line;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl InputError {
+    pub fn new(line: usize, column: usize, text: impl Into<String>, message: impl Display) -> Self {
+        Self {
+            file: None,
+            line,
+            column,
+            text: text.into(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn with_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.file.get_or_insert_with(|| file.as_ref().to_owned());
+        self
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.text)?;
+        write!(f, "    {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Any unparseable line aborts the solution.
+    #[default]
+    Strict,
+    /// Unparseable lines are skipped and collected for reporting.
+    Lenient,
+}
+
+/// Decides what happens to a malformed line, according to the [`Mode`] the input was opened with.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    mode: Mode,
+    skipped: Rc<RefCell<Vec<InputError>>>,
+}
+
+impl Diagnostics {
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            skipped: Rc::default(),
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Passes the error through in strict mode, or records it and yields `None` in lenient mode.
+    pub fn check<T>(&self, result: Result<T, InputError>) -> Result<Option<T>, InputError> {
+        match (result, self.mode) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(err), Mode::Strict) => Err(err),
+            (Err(err), Mode::Lenient) => {
+                let mut skipped = self.skipped.borrow_mut();
+                // The same input may be parsed repeatedly, e.g. when benchmarking.
+                if !skipped.contains(&err) {
+                    skipped.push(err);
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn parse<T>(&self, line: &Line<'_>) -> Result<Option<T>, InputError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.check(line.parse::<T>().map_err(|err| line.error(1, err)))
+    }
+
+    pub fn skipped(&self) -> Vec<InputError> {
+        self.skipped.borrow().clone()
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Input(InputError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Input(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(err: InputError) -> Self {
+        Error::Input(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    #[test]
+    fn test_strict() {
+        let err = Input::from("1\nx\n3\n")
+            .lines()
+            .parsed::<u32>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "x"));
+    }
+
+    #[test]
+    fn test_parsed_with() {
+        let input = Input::new("1 2\n\n3 x\n4 5\n", Diagnostics::new(Mode::Lenient));
+        let parsed = input
+            .lines()
+            .parsed_with(|line| {
+                let (_, second) = line.split_once(' ').unwrap_or_default();
+                second
+                    .parse::<u32>()
+                    .map_err(|err| line.error_at(second, err))
+            })
+            .collect::<Result<Vec<_>, _>>();
+        assert_eq!(parsed, Ok(vec![2, 5]));
+
+        let skipped = input.diagnostics().skipped();
+        assert_eq!((skipped[0].line, skipped[0].column), (3, 3));
+    }
+
+    #[test]
+    fn test_lenient() {
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new("1\nx\n3\ny\n", diagnostics.clone());
+
+        // Parsing the same input twice reports each line only once.
+        for _ in 0..2 {
+            let parsed = input.lines().parsed::<u32>().collect::<Result<Vec<_>, _>>();
+            assert_eq!(parsed, Ok(vec![1, 3]));
+        }
+
+        let skipped = diagnostics.skipped();
+        assert_eq!(
+            skipped.iter().map(|err| err.line).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+    }
+}
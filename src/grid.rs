@@ -0,0 +1,399 @@
+use std::{
+    fmt::{self, Display},
+    iter::FromIterator,
+    ops::{Add, Index, IndexMut},
+};
+
+use crate::{InputError, Lines};
+
+/// A cell position; coordinates are signed so that stepping off the grid is not an overflow.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Pos {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Self::Output {
+        Pos::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Add<Direction> for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+/// Compass directions, with north pointing towards row 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn offset(self) -> Pos {
+        match self {
+            Direction::North => Pos::new(-1, 0),
+            Direction::NorthEast => Pos::new(-1, 1),
+            Direction::East => Pos::new(0, 1),
+            Direction::SouthEast => Pos::new(1, 1),
+            Direction::South => Pos::new(1, 0),
+            Direction::SouthWest => Pos::new(1, -1),
+            Direction::West => Pos::new(0, -1),
+            Direction::NorthWest => Pos::new(-1, -1),
+        }
+    }
+}
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            rows,
+            cols,
+            data: vec![fill; rows * cols],
+        }
+    }
+
+    /// Builds a grid from a character map, one row per non-blank line, mapping each character
+    /// with `f`. Malformed rows are handled according to the input [`Mode`](crate::Mode).
+    pub fn parse<E: Display>(
+        lines: Lines<'_>,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, InputError> {
+        let diagnostics = lines.diagnostics();
+        let mut grid = Grid {
+            rows: 0,
+            cols: 0,
+            data: Vec::new(),
+        };
+
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(col, c)| f(c).map_err(|err| line.error(col + 1, err)))
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|row| match grid.rows {
+                    0 => Ok(row),
+                    _ if row.len() == grid.cols => Ok(row),
+                    _ => Err(line.error(
+                        row.len().min(grid.cols) + 1,
+                        format!("Expected {} cells, found {}", grid.cols, row.len()),
+                    )),
+                });
+
+            if let Some(row) = diagnostics.check(row)? {
+                grid.push_row(row);
+            }
+        }
+
+        Ok(grid)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (0..self.rows as isize).contains(&pos.row) && (0..self.cols as isize).contains(&pos.col)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.data[index])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.data[index])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols as isize;
+        (0..self.rows as isize).flat_map(move |row| (0..cols).map(move |col| Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.data)
+    }
+
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &T> {
+        self.data[row * self.cols..(row + 1) * self.cols].iter()
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.data[col..].iter().step_by(self.cols)
+    }
+
+    /// The cells orthogonally adjacent to `pos` that lie within the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors(pos, &Direction::ORTHOGONAL)
+    }
+
+    /// The cells orthogonally or diagonally adjacent to `pos` that lie within the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        self.neighbors(pos, &Direction::ALL)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: Pos,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Pos, &'a T)> {
+        directions.iter().filter_map(move |&direction| {
+            let next = pos + direction;
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    /// The cells from `pos` towards `direction` up to the edge of the grid, excluding `pos`.
+    pub fn ray(&self, pos: Pos, direction: Direction) -> impl Iterator<Item = (Pos, &T)> {
+        let offset = direction.offset();
+        let mut pos = pos;
+        std::iter::from_fn(move || {
+            pos = pos + offset;
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.cols, self.rows, |pos| Pos::new(pos.col, pos.row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last = self.rows as isize - 1;
+        self.remap(self.cols, self.rows, |pos| {
+            Pos::new(last - pos.col, pos.row)
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last = self.cols as isize - 1;
+        self.remap(self.cols, self.rows, |pos| {
+            Pos::new(pos.col, last - pos.row)
+        })
+    }
+
+    /// Builds a `rows` by `cols` grid whose cell at each position is taken from `source(pos)`.
+    fn remap(&self, rows: usize, cols: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let positions = (0..rows as isize)
+            .flat_map(|row| (0..cols as isize).map(move |col| Pos::new(row, col)));
+        Self {
+            rows,
+            cols,
+            data: positions.map(|pos| self[source(pos)].clone()).collect(),
+        }
+    }
+
+    fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        self.data.extend(row);
+        self.rows += 1;
+        if self.cols == 0 {
+            self.cols = self.data.len();
+        }
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.row as usize * self.cols + pos.col as usize)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside of the grid"))
+    }
+}
+
+impl<E, I: IntoIterator<Item = E>> FromIterator<I> for Grid<E> {
+    /// Collects rows into a grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut grid = Grid {
+            rows: 0,
+            cols: 0,
+            data: Vec::new(),
+        };
+        for row in iter {
+            grid.push_row(row);
+            assert_eq!(grid.data.len(), grid.rows * grid.cols, "ragged grid rows");
+        }
+        grid
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            for cell in self.row(row) {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use crate::{Diagnostics, Input, Mode};
+
+    use super::*;
+
+    fn grid(map: &str) -> Grid<char> {
+        Grid::parse(Input::from(map).lines(), Ok::<_, &str>).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(Input::from("12\n34\n").lines(), |c| {
+            c.to_digit(10).ok_or("Invalid digit")
+        })
+        .unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 2));
+        assert_eq!(grid[Pos::new(1, 0)], 3);
+        assert_eq!(grid.get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.get(Pos::new(0, 2)), None);
+
+        let err = Grid::parse(Input::from("12\n3x\n").lines(), |c| {
+            c.to_digit(10).ok_or("Invalid digit")
+        })
+        .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (2, 2, "Invalid digit")
+        );
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        let err = Grid::parse(Input::from("abc\nab\n").lines(), Ok::<_, &str>).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "Expected 3 cells, found 2");
+
+        let diagnostics = Diagnostics::new(Mode::Lenient);
+        let input = Input::new("abc\nab\ndef\n", diagnostics.clone());
+        let grid = Grid::parse(input.lines(), Ok::<_, &str>).unwrap();
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!(diagnostics.skipped().len(), 1);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid("abc\ndef\nghi\n");
+        let cells = |neighbors: Vec<(Pos, &char)>| {
+            neighbors.into_iter().map(|(_, c)| *c).collect::<String>()
+        };
+
+        assert_eq!(cells(grid.neighbors4(Pos::new(1, 1)).collect()), "bfhd");
+        assert_eq!(cells(grid.neighbors4(Pos::new(0, 0)).collect()), "bd");
+        assert_eq!(cells(grid.neighbors8(Pos::new(1, 1)).collect()), "bcfihgda");
+        assert_eq!(cells(grid.neighbors8(Pos::new(2, 2)).collect()), "fhe");
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = grid("abc\ndef\nghi\n");
+        let ray = |pos, direction| {
+            grid.ray(pos, direction)
+                .map(|(_, c)| *c)
+                .collect::<String>()
+        };
+
+        assert_eq!(ray(Pos::new(2, 1), Direction::North), "eb");
+        assert_eq!(ray(Pos::new(1, 0), Direction::East), "ef");
+        assert_eq!(ray(Pos::new(0, 0), Direction::SouthEast), "ei");
+        assert_eq!(ray(Pos::new(0, 0), Direction::West), "");
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid(indoc! {"
+            abc
+            def
+        "});
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
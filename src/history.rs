@@ -0,0 +1,283 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, InputError, Phase, Timing};
+
+/// One timed phase of a benchmark run, as stored in the history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Milliseconds since the Unix epoch at which the run started; shared by all its records.
+    pub run: u64,
+    pub commit: String,
+    #[serde(flatten)]
+    pub timing: Timing,
+}
+
+/// Benchmark runs persisted as JSON lines, oldest first.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct History {
+    records: Vec<Record>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("bench-history.jsonl")
+    }
+
+    /// Loads the file at `path`, treating a missing file as an empty history.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let records = contents
+            .lines()
+            .zip(1..)
+            .filter(|(text, _)| !text.trim().is_empty())
+            .map(|(text, number)| {
+                serde_json::from_str(text)
+                    .map_err(|err| InputError::new(number, err.column(), text, err).with_file(path))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { records })
+    }
+
+    /// Appends a run to the file at `path`, creating it if needed.
+    pub fn append(path: impl AsRef<Path>, records: &[Record]) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for record in records {
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        Ok(())
+    }
+
+    pub fn record(timings: Vec<Timing>) -> Vec<Record> {
+        let run = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let commit = current_commit();
+
+        timings
+            .into_iter()
+            .map(|timing| Record {
+                run,
+                commit: commit.clone(),
+                timing,
+            })
+            .collect()
+    }
+
+    /// Run ids, oldest first.
+    pub fn runs(&self) -> Vec<u64> {
+        let mut runs = self
+            .records
+            .iter()
+            .map(|record| record.run)
+            .collect::<Vec<_>>();
+        runs.dedup();
+        runs
+    }
+
+    /// The latest run other than `candidate` whose commit starts with `commit`. A clean commit
+    /// is a prefix of its own `-dirty` runs, which may include the candidate itself.
+    pub fn find_run(&self, commit: &str, candidate: u64) -> Option<u64> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.run != candidate && record.commit.starts_with(commit))
+            .map(|record| record.run)
+    }
+
+    /// The latest run before `candidate` that timed one of `days` in a phase `candidate` also
+    /// timed, so that comparing the two runs compares something.
+    pub fn previous_run(&self, candidate: u64, days: &[u8]) -> Option<u64> {
+        let timed = self
+            .run(candidate)
+            .map(|record| (record.timing.day, record.timing.phase))
+            .filter(|(day, _)| days.contains(day))
+            .collect::<Vec<_>>();
+        self.records
+            .iter()
+            .rev()
+            .skip_while(|record| record.run >= candidate)
+            .find(|record| timed.contains(&(record.timing.day, record.timing.phase)))
+            .map(|record| record.run)
+    }
+
+    pub fn commit(&self, run: u64) -> Option<&str> {
+        self.run(run).next().map(|record| record.commit.as_str())
+    }
+
+    pub fn run(&self, run: u64) -> impl Iterator<Item = &Record> {
+        self.records.iter().filter(move |record| record.run == run)
+    }
+
+    /// Compares the median of every day and phase present in both runs.
+    pub fn compare(&self, baseline: u64, candidate: u64, threshold: f64) -> Vec<Comparison> {
+        let medians = |run| {
+            self.run(run)
+                .map(|record| {
+                    let timing = &record.timing;
+                    ((timing.day, timing.phase), timing.stats.median)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let baseline = medians(baseline);
+        medians(candidate)
+            .into_iter()
+            .filter_map(|((day, phase), candidate)| {
+                let baseline = *baseline.get(&(day, phase))?;
+                Some(Comparison {
+                    day,
+                    phase,
+                    baseline,
+                    candidate,
+                    regressed: candidate.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub candidate: Duration,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    /// Relative change of the candidate median over the baseline one.
+    pub fn change(&self) -> f64 {
+        self.candidate.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+/// Short hash of the checked out commit, suffixed with `-dirty` when the tree has changes.
+fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+
+    match (
+        git(&["rev-parse", "--short", "HEAD"]),
+        git(&["status", "--porcelain"]),
+    ) {
+        (Some(commit), Some(status)) if !status.is_empty() => format!("{commit}-dirty"),
+        (Some(commit), _) => commit,
+        (None, _) => "unknown".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Stats;
+
+    use super::*;
+
+    fn record(run: u64, commit: &str, day: u8, phase: Phase, median_ms: u64) -> Record {
+        let median = Duration::from_millis(median_ms);
+        Record {
+            run,
+            commit: commit.to_owned(),
+            timing: Timing {
+                day,
+                phase,
+                iterations: 1,
+                stats: Stats::from_samples(&[median]),
+            },
+        }
+    }
+
+    fn history() -> History {
+        History {
+            records: vec![
+                record(1, "aaaaaaa", 8, Phase::Part2, 100),
+                record(1, "aaaaaaa", 11, Phase::Part2, 100),
+                record(2, "bbbbbbb", 8, Phase::Part2, 105),
+                record(2, "bbbbbbb", 11, Phase::Part2, 150),
+                record(2, "bbbbbbb", 1, Phase::Parse, 1),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut history = history();
+        assert_eq!(history.runs(), vec![1, 2]);
+        assert_eq!(history.find_run("aaa", 2), Some(1));
+        assert_eq!(history.find_run("ccc", 2), None);
+        assert_eq!(history.find_run("bbb", 2), None);
+
+        history
+            .records
+            .push(record(3, "aaaaaaa-dirty", 8, Phase::Part2, 90));
+        assert_eq!(history.find_run("aaaaaaa", 3), Some(1));
+        assert_eq!(history.find_run("aaaaaaa", 2), Some(3));
+        assert_eq!(history.commit(2), Some("bbbbbbb"));
+    }
+
+    #[test]
+    fn test_previous_run() {
+        let mut history = history();
+        assert_eq!(history.previous_run(2, &[8, 11]), Some(1));
+        assert_eq!(history.previous_run(2, &[1]), None);
+        assert_eq!(history.previous_run(1, &[8]), None);
+
+        history
+            .records
+            .push(record(3, "ccccccc", 1, Phase::Parse, 1));
+        history
+            .records
+            .push(record(4, "ddddddd", 8, Phase::Part2, 90));
+        assert_eq!(history.previous_run(4, &[8]), Some(2));
+        assert_eq!(history.previous_run(3, &[1, 8]), Some(2));
+    }
+
+    #[test]
+    fn test_compare() {
+        let comparisons = history().compare(1, 2, 0.1);
+        let regressed = comparisons
+            .iter()
+            .map(|c| (c.day, c.regressed))
+            .collect::<Vec<_>>();
+        assert_eq!(regressed, vec![(8, false), (11, true)]);
+        assert!((comparisons[1].change() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("advent-history-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("history.jsonl");
+
+        let history = history();
+        History::append(&path, &history.records[..2]).unwrap();
+        History::append(&path, &history.records[2..]).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
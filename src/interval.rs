@@ -0,0 +1,485 @@
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+    iter::FromIterator,
+    str::FromStr,
+};
+
+/// A non-empty range of integers with both bounds included, such as a range of sections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    start: usize,
+    end: usize,
+}
+
+impl Interval {
+    /// The interval from `start` to `end` inclusive, or `None` if `start` is after `end`.
+    pub const fn new(start: usize, end: usize) -> Option<Self> {
+        if start <= end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    pub const fn single(value: usize) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Number of integers in the interval, which exceeds `usize::MAX` for `0..=usize::MAX`.
+    #[allow(clippy::len_without_is_empty)] // never empty
+    pub const fn len(&self) -> u128 {
+        (self.end - self.start) as u128 + 1
+    }
+
+    pub const fn contains(&self, value: usize) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every integer of `other` is also in `self`.
+    pub const fn includes(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub const fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The interval spanning both, if they overlap or are adjacent so that it leaves no gap.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first.end.saturating_add(1) < second.start {
+            return None;
+        }
+        Some(Self {
+            start: first.start,
+            end: first.end.max(second.end),
+        })
+    }
+
+    /// Parses `start`, `separator` and `end`.
+    pub fn parse_with(s: &str, separator: &str) -> Result<Self, String> {
+        let (start, end) = s
+            .split_once(separator)
+            .ok_or_else(|| format!("Expected start{separator}end, found {s:?}"))?;
+        let bound = |bound: &str, name| {
+            bound
+                .trim()
+                .parse::<usize>()
+                .map_err(|err| format!("Invalid {name} {bound:?}: {err}"))
+        };
+        let (start, end) = (bound(start, "start")?, bound(end, "end")?);
+        Self::new(start, end).ok_or_else(|| format!("Reversed range {s:?}"))
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl FromStr for Interval {
+    type Err = String;
+
+    /// Parses `start-end`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, "-")
+    }
+}
+
+/// A set of integers, as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the integers of `interval`, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval) {
+        let first = self
+            .intervals
+            .partition_point(|other| other.end.saturating_add(1) < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end.saturating_add(1));
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, other| merged.union(other).unwrap());
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            interval.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// How many of a collection of intervals cover each integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    intervals: Vec<Interval>,
+    /// Maximal runs of integers with the same depth, from the first integer covered to the last.
+    segments: Vec<(Interval, usize)>,
+}
+
+impl Coverage {
+    pub fn new(intervals: impl IntoIterator<Item = Interval>) -> Self {
+        let intervals = intervals.into_iter().collect::<Vec<_>>();
+
+        // Ends are moved one past the interval, which may be one past `usize::MAX`.
+        let mut events = intervals
+            .iter()
+            .flat_map(|interval| [(interval.start as u128, 1), (interval.end as u128 + 1, -1)])
+            .collect::<Vec<(u128, isize)>>();
+        events.sort_unstable();
+
+        let mut segments = Vec::<(Interval, usize)>::new();
+        let mut depth = 0;
+        for (i, (position, delta)) in events.iter().enumerate() {
+            depth += delta;
+            let Some((next, _)) = events.get(i + 1).filter(|(next, _)| next > position) else {
+                continue;
+            };
+            let segment = Interval {
+                start: *position as usize,
+                end: (next - 1) as usize,
+            };
+            match segments.last_mut() {
+                Some((last, last_depth)) if *last_depth == depth as usize => last.end = segment.end,
+                _ => segments.push((segment, depth as usize)),
+            }
+        }
+
+        Self {
+            intervals,
+            segments,
+        }
+    }
+
+    pub fn segments(&self) -> &[(Interval, usize)] {
+        &self.segments
+    }
+
+    /// The integers from the first covered to the last, if any is.
+    pub fn span(&self) -> Option<Interval> {
+        let (first, _) = self.segments.first()?;
+        let (last, _) = self.segments.last()?;
+        Interval::new(first.start, last.end)
+    }
+
+    /// The integers of the span covered by a number of intervals accepted by `depth`.
+    pub fn where_depth(&self, depth: impl Fn(usize) -> bool) -> IntervalSet {
+        self.segments
+            .iter()
+            .filter(|(_, count)| depth(*count))
+            .map(|(segment, _)| *segment)
+            .collect()
+    }
+
+    /// The largest number of intervals covering a same integer, and where that happens.
+    pub fn max_depth(&self) -> (usize, IntervalSet) {
+        let max = self
+            .segments
+            .iter()
+            .map(|(_, depth)| *depth)
+            .max()
+            .unwrap_or_default();
+        (max, self.where_depth(|depth| depth == max))
+    }
+
+    /// Positions of a smallest set of the intervals covering every covered integer, found by
+    /// repeatedly taking the interval that extends the covered run the furthest, or the first
+    /// listed of those that extend it as far.
+    pub fn cover(&self) -> Vec<usize> {
+        let mut order = (0..self.intervals.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| (self.intervals[i].start, Reverse(self.intervals[i].end)));
+
+        let mut cover = Vec::new();
+        let mut covered = None::<usize>;
+        let mut order = order.into_iter().peekable();
+        while let Some(&next) = order.peek() {
+            let next = self.intervals[next];
+            if covered.is_some_and(|end| next.end <= end) {
+                order.next();
+                continue;
+            }
+
+            // The first integer left to cover, which `next` is the first interval to reach.
+            let from = match covered {
+                Some(end) if next.start <= end + 1 => end + 1,
+                _ => next.start,
+            };
+            let mut best = None::<usize>;
+            while let Some(i) = order.next_if(|&i| self.intervals[i].start <= from) {
+                if best.is_none_or(|best| self.intervals[i].end > self.intervals[best].end) {
+                    best = Some(i);
+                }
+            }
+
+            let best = best.expect("next starts at or before from");
+            covered = Some(self.intervals[best].end);
+            cover.push(best);
+        }
+
+        cover.sort_unstable();
+        cover
+    }
+
+    /// Positions of the intervals left out of [`cover`](Self::cover), which may all be dropped
+    /// without uncovering any integer.
+    pub fn redundant(&self) -> Vec<usize> {
+        let cover = self.cover();
+        (0..self.intervals.len())
+            .filter(|i| cover.binary_search(i).is_err())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn interval(start: usize, end: usize) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("2-8".parse(), Ok(interval(2, 8)));
+        assert_eq!("6-6".parse(), Ok(Interval::single(6)));
+        assert_eq!(
+            "6".parse::<Interval>(),
+            Err("Expected start-end, found \"6\"".to_owned())
+        );
+        assert_eq!(
+            "8-2".parse::<Interval>(),
+            Err("Reversed range \"8-2\"".to_owned())
+        );
+        assert!("2-".parse::<Interval>().is_err());
+        assert!("-2".parse::<Interval>().is_err());
+        assert!(format!("0-{}0", usize::MAX).parse::<Interval>().is_err());
+        assert_eq!(Interval::parse_with("2..8", ".."), Ok(interval(2, 8)));
+        assert_eq!(Interval::parse_with("2 to 2", "to"), Ok(interval(2, 2)));
+        assert!(Interval::parse_with("2-8", "..").is_err());
+        assert_eq!(
+            Interval::parse_with("8 .. 2", ".."),
+            Err("Reversed range \"8 .. 2\"".to_owned())
+        );
+        assert_eq!(interval(4, 5).to_string(), "4-5");
+    }
+
+    #[test]
+    fn test_relations() {
+        let (a, b, c) = (interval(2, 8), interval(3, 7), interval(8, 9));
+        assert!(a.includes(&b) && !b.includes(&a) && a.includes(&a));
+        assert!(a.overlaps(&c) && c.overlaps(&a) && !b.overlaps(&c));
+        assert!(a.contains(2) && a.contains(8) && !a.contains(9));
+        assert_eq!(a.intersection(&c), Some(Interval::single(8)));
+        assert_eq!(b.intersection(&c), None);
+
+        assert_eq!(b.union(&c), Some(interval(3, 9)));
+        assert_eq!(c.union(&b), Some(interval(3, 9)));
+        assert_eq!(interval(1, 2).union(&interval(4, 5)), None);
+        assert_eq!(a.len(), 7);
+        assert_eq!(Interval::single(0).len(), 1);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let max = usize::MAX;
+        let all = interval(0, max);
+        let top = Interval::single(max);
+
+        assert_eq!(all.len(), max as u128 + 1);
+        assert_eq!(format!("0-{max}").parse(), Ok(all));
+        assert!(all.includes(&top) && all.contains(max));
+        assert_eq!(all.intersection(&top), Some(top));
+        assert_eq!(
+            interval(max - 1, max).union(&top),
+            Some(interval(max - 1, max))
+        );
+        assert_eq!(interval(0, max - 2).union(&top), None);
+        assert_eq!(interval(0, max - 1).union(&top), Some(all));
+        assert_eq!(top.union(&Interval::single(0)), None);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut set = IntervalSet::new();
+        for interval in [
+            interval(10, 12),
+            interval(1, 3),
+            interval(5, 5),
+            interval(20, 30),
+        ] {
+            set.insert(interval);
+        }
+        assert_eq!(set.to_string(), "1-3, 5-5, 10-12, 20-30");
+        assert_eq!(set.len(), 3 + 1 + 3 + 11);
+
+        set.insert(interval(4, 11));
+        assert_eq!(set.to_string(), "1-12, 20-30");
+        assert!(set.contains(12) && !set.contains(13) && !set.contains(0));
+
+        set.insert(interval(0, usize::MAX));
+        assert_eq!(set.intervals(), [interval(0, usize::MAX)]);
+        assert!(IntervalSet::new().is_empty());
+    }
+
+    #[test]
+    fn test_coverage() {
+        let coverage = Coverage::new([
+            interval(2, 4),
+            interval(6, 8),
+            interval(2, 3),
+            interval(4, 5),
+            interval(6, 6),
+            interval(4, 6),
+            interval(12, 12),
+        ]);
+        assert_eq!(coverage.span(), Some(interval(2, 12)));
+        assert_eq!(coverage.where_depth(|depth| depth == 0).to_string(), "9-11");
+        assert_eq!(
+            coverage.where_depth(|depth| depth == 1).to_string(),
+            "7-8, 12-12"
+        );
+        assert_eq!(
+            coverage.where_depth(|depth| depth > 2).to_string(),
+            "4-4, 6-6"
+        );
+        let (max, at) = coverage.max_depth();
+        assert_eq!((max, at.to_string()), (3, "4-4, 6-6".to_owned()));
+        assert_eq!(coverage.cover(), vec![0, 1, 5, 6]);
+        assert_eq!(coverage.redundant(), vec![2, 3, 4]);
+
+        let empty = Coverage::new([]);
+        assert_eq!(empty.span(), None);
+        assert_eq!(empty.max_depth(), (0, IntervalSet::new()));
+        assert!(empty.cover().is_empty());
+    }
+
+    #[test]
+    fn test_coverage_extremes() {
+        let max = usize::MAX;
+        let coverage = Coverage::new([interval(0, max), interval(max, max), interval(0, 0)]);
+        assert_eq!(
+            coverage.segments(),
+            [
+                (interval(0, 0), 2),
+                (interval(1, max - 1), 1),
+                (interval(max, max), 2)
+            ]
+        );
+        assert_eq!(coverage.redundant(), vec![1, 2]);
+    }
+
+    fn intervals() -> impl Strategy<Value = Vec<Interval>> {
+        prop::collection::vec(
+            (0..40usize, 0..8usize).prop_map(|(start, len)| interval(start, start + len)),
+            0..8,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_set(intervals in intervals()) {
+            let set = intervals.iter().copied().collect::<IntervalSet>();
+            for pair in set.intervals().windows(2) {
+                prop_assert!(pair[0].end + 1 < pair[1].start);
+            }
+            for value in 0..50 {
+                let expected = intervals.iter().any(|interval| interval.contains(value));
+                prop_assert_eq!(set.contains(value), expected);
+            }
+        }
+
+        #[test]
+        fn prop_coverage(intervals in intervals()) {
+            let coverage = Coverage::new(intervals.clone());
+            for (segment, depth) in coverage.segments() {
+                for value in segment.start..=segment.end {
+                    let count = intervals.iter().filter(|interval| interval.contains(value)).count();
+                    prop_assert_eq!(count, *depth);
+                }
+            }
+
+            // No smaller subset covers the same integers as the chosen cover.
+            let union = intervals.iter().copied().collect::<IntervalSet>();
+            let cover = coverage.cover();
+            let covers = |subset: &[usize]| {
+                subset.iter().map(|&i| intervals[i]).collect::<IntervalSet>() == union
+            };
+            prop_assert!(covers(&cover));
+            let smaller = (0..intervals.len())
+                .combinations(cover.len().saturating_sub(1))
+                .any(|subset| covers(&subset));
+            prop_assert!(cover.is_empty() || !smaller);
+        }
+    }
+}
@@ -0,0 +1,416 @@
+//! Iterator adaptors shared by the solutions.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    hash::Hash,
+};
+
+pub trait IteratorExt: Iterator {
+    /// Yields every window of `N` consecutive items, as owned arrays.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    fn overlapping_chunks<const N: usize>(self) -> OverlappingChunks<Self, N>
+    where
+        Self: Sized,
+    {
+        assert!(N > 0, "window size must be positive");
+        OverlappingChunks {
+            iter: self,
+            window: None,
+        }
+    }
+
+    /// Like [`Iterator::take_while`], but also yields the first item failing the predicate.
+    fn take_while_inclusive<P>(self, predicate: P) -> TakeWhileInclusive<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        TakeWhileInclusive {
+            iter: self,
+            finished: false,
+            predicate,
+        }
+    }
+
+    /// Windows of `size` consecutive items, lent out as slices so that items need not be `Clone`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    fn sliding_windows(self, size: usize) -> SlidingWindows<Self>
+    where
+        Self: Sized,
+    {
+        assert!(size > 0, "window size must be positive");
+        SlidingWindows {
+            iter: self,
+            buf: Vec::with_capacity(size * 2),
+            start: 0,
+            size,
+        }
+    }
+
+    /// Position of the first item equal to an item before it.
+    fn first_duplicate_position(mut self) -> Option<usize>
+    where
+        Self: Sized,
+        Self::Item: Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        self.position(|item| !seen.insert(item))
+    }
+
+    /// The `k` largest items, largest first.
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k.min(self.size_hint().0) + 1);
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// Yields consecutive, non-overlapping arrays of `N` items; a shorter trailing chunk is kept
+    /// aside and available from [`ChunksExact::into_remainder`].
+    fn chunks_exact<const N: usize>(self) -> ChunksExact<Self, N>
+    where
+        Self: Sized,
+    {
+        ChunksExact {
+            iter: self,
+            remainder: Vec::new(),
+        }
+    }
+}
+
+impl<T> IteratorExt for T where T: Iterator {}
+
+pub struct OverlappingChunks<I: Iterator, const N: usize> {
+    iter: I,
+    /// The last window yielded, kept so that the next one only needs a single new item.
+    window: Option<[I::Item; N]>,
+}
+
+impl<I, const N: usize> Iterator for OverlappingChunks<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.window {
+            Some(window) => {
+                let item = self.iter.next()?;
+                window.rotate_left(1);
+                window[N - 1] = item;
+            }
+            None => {
+                let iter = &mut self.iter;
+                let first = std::array::from_fn::<_, N, _>(|_| iter.next());
+                if !first.iter().all(Option::is_some) {
+                    return None;
+                }
+                self.window = Some(first.map(Option::unwrap));
+            }
+        }
+        self.window.clone()
+    }
+}
+
+pub struct TakeWhileInclusive<I, P> {
+    iter: I,
+    finished: bool,
+    predicate: P,
+}
+
+impl<I: Iterator, P> Iterator for TakeWhileInclusive<I, P>
+where
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.finished {
+            None
+        } else {
+            let x = self.iter.next()?;
+            self.finished = !(self.predicate)(&x);
+            Some(x)
+        }
+    }
+}
+
+/// A lending iterator over windows; see [`IteratorExt::sliding_windows`].
+pub struct SlidingWindows<I: Iterator> {
+    iter: I,
+    buf: Vec<I::Item>,
+    start: usize,
+    size: usize,
+}
+
+impl<I: Iterator> SlidingWindows<I> {
+    /// The next window, which borrows from the adaptor until the following call.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&[I::Item]> {
+        if self.buf.len() - self.start == self.size {
+            self.start += 1;
+        }
+
+        // Drop consumed items once they fill half the buffer, so each item moves at most once.
+        if self.start >= self.size {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+
+        while self.buf.len() - self.start < self.size {
+            self.buf.push(self.iter.next()?);
+        }
+
+        Some(&self.buf[self.start..])
+    }
+}
+
+pub struct ChunksExact<I: Iterator, const N: usize> {
+    iter: I,
+    remainder: Vec<I::Item>,
+}
+
+impl<I: Iterator, const N: usize> ChunksExact<I, N> {
+    /// The trailing items that did not fill a whole chunk, once the adaptor is exhausted.
+    pub fn into_remainder(self) -> Vec<I::Item> {
+        self.remainder
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for ChunksExact<I, N> {
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = std::array::from_fn::<_, N, _>(|_| self.iter.next());
+        if chunk.iter().all(Option::is_some) {
+            Some(chunk.map(Option::unwrap))
+        } else {
+            self.remainder.extend(chunk.into_iter().flatten());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_overlapping_chunks() {
+        let chunks = (1..=4).overlapping_chunks::<3>().collect::<Vec<_>>();
+        assert_eq!(chunks, vec![[1, 2, 3], [2, 3, 4]]);
+        assert_eq!((1..=2).overlapping_chunks::<3>().next(), None);
+    }
+
+    /// An item counting its live instances, to catch leaked or doubly dropped items.
+    #[derive(Debug)]
+    struct Tracked(Rc<Cell<isize>>);
+
+    impl Tracked {
+        fn new(live: &Rc<Cell<isize>>) -> Self {
+            live.set(live.get() + 1);
+            Tracked(live.clone())
+        }
+    }
+
+    impl Clone for Tracked {
+        fn clone(&self) -> Self {
+            Tracked::new(&self.0)
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_chunks_owned() {
+        let words = ["a", "b", "c", "d"].map(String::from);
+        let chunks = words.into_iter().overlapping_chunks::<2>();
+        assert_eq!(
+            chunks.map(|chunk| chunk.concat()).collect::<Vec<_>>(),
+            vec!["ab", "bc", "cd"]
+        );
+
+        let items = (0..5).map(Rc::new).collect::<Vec<_>>();
+        let chunks = items
+            .iter()
+            .cloned()
+            .overlapping_chunks::<3>()
+            .collect::<Vec<_>>();
+        assert_eq!(chunks[2].clone().map(|n| *n), [2, 3, 4]);
+        drop(chunks);
+        assert!(items.iter().all(|item| Rc::strong_count(item) == 1));
+    }
+
+    #[test]
+    fn test_overlapping_chunks_drops() {
+        let live = Rc::new(Cell::new(0));
+
+        let chunks = (0..10)
+            .map(|_| Tracked::new(&live))
+            .overlapping_chunks::<4>();
+        assert_eq!(chunks.count(), 7);
+        assert_eq!(live.get(), 0);
+
+        // Dropping the adaptor part way through drops the window it holds.
+        let mut chunks = (0..10)
+            .map(|_| Tracked::new(&live))
+            .overlapping_chunks::<4>();
+        let chunk = chunks.nth(2);
+        drop(chunks);
+        assert_eq!(live.get(), 4);
+        drop(chunk);
+        assert_eq!(live.get(), 0);
+
+        // A short input is dropped without yielding anything.
+        let mut chunks = (0..3)
+            .map(|_| Tracked::new(&live))
+            .overlapping_chunks::<4>();
+        assert!(chunks.next().is_none());
+        assert_eq!(live.get(), 0);
+    }
+
+    #[test]
+    fn test_overlapping_chunks_large() {
+        let chunks = (0..100u16).overlapping_chunks::<64>().collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 37);
+        assert!(chunks[36].iter().copied().eq(36..100));
+        assert_eq!(
+            (0..3).overlapping_chunks::<1>().collect::<Vec<_>>(),
+            vec![[0], [1], [2]]
+        );
+    }
+
+    #[test]
+    fn test_take_while_inclusive() {
+        let taken = [1, 2, 5, 3].into_iter().take_while_inclusive(|n| *n < 4);
+        assert_eq!(taken.collect::<Vec<_>>(), vec![1, 2, 5]);
+
+        let taken = [5, 1].into_iter().take_while_inclusive(|n| *n < 4);
+        assert_eq!(taken.collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn test_sliding_windows() {
+        let mut windows = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(String::from)
+            .sliding_windows(2);
+        assert_eq!(windows.next(), Some(&["a".into(), "b".into()][..]));
+        assert_eq!(windows.next(), Some(&["b".into(), "c".into()][..]));
+        assert_eq!(windows.next(), Some(&["c".into(), "d".into()][..]));
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn test_first_duplicate_position() {
+        assert_eq!("abcb".chars().first_duplicate_position(), Some(3));
+        assert_eq!("abc".chars().first_duplicate_position(), None);
+    }
+
+    #[test]
+    fn test_top_k() {
+        assert_eq!([3, 1, 4, 1, 5].into_iter().top_k(3), vec![5, 4, 3]);
+        assert_eq!([3, 1].into_iter().top_k(3), vec![3, 1]);
+        assert_eq!([3, 1].into_iter().top_k(0), Vec::<i32>::new());
+        assert_eq!([3, 1].into_iter().top_k(usize::MAX), vec![3, 1]);
+    }
+
+    #[test]
+    fn test_chunks_exact() {
+        let mut chunks = (1..=7).chunks_exact::<3>();
+        assert_eq!(
+            chunks.by_ref().collect::<Vec<_>>(),
+            vec![[1, 2, 3], [4, 5, 6]]
+        );
+        assert_eq!(chunks.into_remainder(), vec![7]);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_overlapping_chunks(items in prop::collection::vec(any::<u8>(), 0..64)) {
+            let chunks = items.iter().copied().overlapping_chunks::<4>().collect::<Vec<_>>();
+            let expected = items.windows(4).map(|w| [w[0], w[1], w[2], w[3]]).collect::<Vec<_>>();
+            prop_assert_eq!(chunks, expected);
+        }
+
+        #[test]
+        fn prop_overlapping_chunks_owned(items in prop::collection::vec(".{0,3}", 0..32)) {
+            let chunks = items.iter().cloned().overlapping_chunks::<5>().collect::<Vec<_>>();
+            let expected = items.windows(5).map(|w| <&[String; 5]>::try_from(w).unwrap().clone()).collect::<Vec<_>>();
+            prop_assert_eq!(chunks, expected);
+        }
+
+        #[test]
+        fn prop_take_while_inclusive(items in prop::collection::vec(0..10u8, 0..64), limit in 0..10u8) {
+            let taken = items.iter().take_while_inclusive(|n| **n < limit).count();
+            let expected = items
+                .iter()
+                .position(|n| *n >= limit)
+                .map_or(items.len(), |pos| pos + 1);
+            prop_assert_eq!(taken, expected);
+        }
+
+        #[test]
+        fn prop_sliding_windows(items in prop::collection::vec(any::<u8>(), 0..64), size in 1..8usize) {
+            let mut windows = items.iter().copied().sliding_windows(size);
+            let mut expected = items.windows(size);
+            loop {
+                let window = windows.next();
+                prop_assert_eq!(window, expected.next());
+                if window.is_none() {
+                    break;
+                }
+            }
+        }
+
+        #[test]
+        fn prop_first_duplicate_position(items in prop::collection::vec(0..16u8, 0..32)) {
+            let expected = (0..items.len()).find(|&i| items[..i].contains(&items[i]));
+            prop_assert_eq!(items.iter().first_duplicate_position(), expected);
+        }
+
+        #[test]
+        fn prop_top_k(items in prop::collection::vec(any::<i32>(), 0..64), k in 0..8usize) {
+            let mut expected = items.clone();
+            expected.sort_by(|a, b| b.cmp(a));
+            expected.truncate(k);
+            prop_assert_eq!(items.into_iter().top_k(k), expected);
+        }
+
+        #[test]
+        fn prop_chunks_exact(items in prop::collection::vec(any::<u8>(), 0..64)) {
+            let mut chunks = items.iter().copied().chunks_exact::<3>();
+            let flat = chunks.by_ref().flatten().collect::<Vec<_>>();
+            let remainder = chunks.into_remainder();
+            prop_assert_eq!(flat.len(), items.len() / 3 * 3);
+            prop_assert_eq!([flat, remainder].concat(), items);
+        }
+    }
+}
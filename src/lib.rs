@@ -1,25 +1,763 @@
 use std::{
+    fmt::{self, Display},
     fs,
-    io::{self, BufRead, BufReader, Read},
-    path::Path,
+    io::{self, BufRead, Read},
+    iter::Zip,
+    ops::{Deref, RangeFrom},
+    path::{Path, PathBuf},
+    str::{self, FromStr},
 };
 
-pub fn input_lines(path: impl AsRef<Path>) -> io::Result<impl Iterator<Item = String>> {
-    Ok(BufReader::new(fs::File::open(path)?)
-        .lines()
-        .filter_map(|line| line.ok()))
+pub use answers::AnswerFile;
+pub use bench::{BenchOptions, Phase, Stats, Timing};
+pub use error::{Diagnostics, Error, InputError, Mode};
+pub use grid::{Direction, Grid, Pos};
+pub use history::{Comparison, History, Record};
+pub use interval::{Coverage, Interval, IntervalSet};
+pub use source::Source;
+
+pub mod iter;
+pub mod marker;
+
+mod answers;
+mod bench;
+mod error;
+mod grid;
+mod history;
+mod interval;
+mod source;
+
+pub trait Solution {
+    const DAY: u8;
+
+    /// The parsed input, which may borrow from the [`Input`] it was parsed from.
+    type Parsed<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &Input) -> Result<Self::Parsed<'_>, InputError>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Self::Answer1, InputError>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Self::Answer2, InputError>;
+}
+
+/// A whole puzzle input, read once and handed out as borrowed lines or bytes.
+#[derive(Debug, Clone)]
+pub struct Input {
+    text: String,
+    diagnostics: Diagnostics,
+    config: Option<Config>,
+}
+
+impl Input {
+    pub fn new(text: impl Into<String>, diagnostics: Diagnostics) -> Self {
+        Self {
+            text: text.into(),
+            diagnostics,
+            config: None,
+        }
+    }
+
+    pub fn with_config(self, config: Option<Config>) -> Self {
+        Self { config, ..self }
+    }
+
+    pub fn read(source: &Source, diagnostics: &Diagnostics) -> io::Result<Self> {
+        let mut text = String::new();
+        source
+            .open()?
+            .read_to_string(&mut text)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::InvalidData => {
+                    io::Error::new(err.kind(), format!("{source}: {err}"))
+                }
+                _ => err,
+            })?;
+        Ok(Self::new(text, diagnostics.clone()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            lines: self.text.lines().zip(1..),
+            diagnostics: self.diagnostics.clone(),
+        }
+    }
+
+    pub fn diagnostics(&self) -> Diagnostics {
+        self.diagnostics.clone()
+    }
+
+    /// The configuration the input was given with, if any.
+    pub fn config(&self) -> Option<&Config> {
+        self.config.as_ref()
+    }
+
+    /// Groups the lines into blocks separated by one or more blank lines.
+    pub fn blocks(&self) -> Blocks<'_> {
+        Blocks {
+            text: &self.text,
+            lines: self.lines(),
+            index: 0,
+        }
+    }
+}
+
+impl From<&str> for Input {
+    /// Wraps text in a strict input, as used by the examples in tests.
+    fn from(text: &str) -> Self {
+        Self::new(text, Diagnostics::default())
+    }
+}
+
+/// A file changing how a day interprets its input, such as the rules of a game.
+#[derive(Debug, Clone)]
+pub struct Config {
+    path: PathBuf,
+    text: String,
+}
+
+impl Config {
+    pub fn new(path: impl Into<PathBuf>, text: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            text: text.into(),
+        }
+    }
+
+    pub fn read(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let text = fs::read_to_string(&path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+        Ok(Self::new(path, text))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The lines of the configuration, which is always parsed strictly.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            lines: self.text.lines().zip(1..),
+            diagnostics: Diagnostics::default(),
+        }
+    }
+
+    /// The `key = value` lines of the configuration, skipping blank lines and `#` comments. A
+    /// value in double quotes is taken as is, so that it may hold spaces or `#`.
+    pub fn entries(&self) -> Result<Vec<Entry<'_>>, InputError> {
+        let mut entries = Vec::<Entry>::new();
+
+        for line in self.lines() {
+            let text = line.text.split('#').next().unwrap_or_default();
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let (key, value) = text
+                .split_once('=')
+                .ok_or_else(|| line.error(1, "Expected key = value"))?;
+            let value = match line.text[key.len() + 1..].trim_start() {
+                quoted if quoted.starts_with('"') => {
+                    let (value, rest) = quoted[1..]
+                        .split_once('"')
+                        .ok_or_else(|| line.error_at(quoted, "Unclosed quote"))?;
+                    let rest = rest.split('#').next().unwrap_or_default().trim();
+                    if !rest.is_empty() {
+                        return Err(line.error_at(rest, "Unexpected text after the quoted value"));
+                    }
+                    value
+                }
+                _ => value.trim(),
+            };
+            let entry = Entry {
+                line,
+                key: key.trim(),
+                value,
+            };
+            if let Some(previous) = entries.iter().find(|previous| previous.key == entry.key) {
+                return Err(entry.error(
+                    entry.key,
+                    format!(
+                        "Duplicate key {}, already defined on line {}",
+                        entry.key, previous.line.number
+                    ),
+                ));
+            }
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// An error about something missing from the configuration, shown past its last line.
+    pub fn missing(&self, message: impl Display) -> InputError {
+        InputError::new(self.text.lines().count() + 1, 1, "", message)
+    }
+}
+
+/// A `key = value` line of a [`Config`], with both sides trimmed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub line: Line<'a>,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+impl Entry<'_> {
+    /// An error shown at `part`, which must be a slice of the line such as the key or value.
+    pub fn error(&self, part: &str, message: impl Display) -> InputError {
+        self.line.error_at(part, message)
+    }
+
+    pub fn parse<T>(&self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.value
+            .parse()
+            .map_err(|err| self.error(self.value, format!("Invalid {}: {err}", self.key)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl Line<'_> {
+    pub fn error(&self, column: usize, message: impl Display) -> InputError {
+        InputError::new(self.number, column, self.text, message)
+    }
+
+    /// An error shown at `part`, a slice of the line such as an offending token, or at column 1
+    /// if `part` is not part of the line.
+    pub fn error_at(&self, part: &str, message: impl Display) -> InputError {
+        let start = self.text.as_ptr() as usize;
+        let column = (part.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + part.len() <= self.text.len())
+            .map_or(0, |offset| self.text[..offset].chars().count());
+        self.error(column + 1, message)
+    }
+}
+
+impl Deref for Line<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.text
+    }
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.text.fmt(f)
+    }
+}
+
+pub struct Lines<'a> {
+    lines: Zip<str::Lines<'a>, RangeFrom<usize>>,
+    diagnostics: Diagnostics,
+}
+
+impl<'a> Lines<'a> {
+    pub fn diagnostics(&self) -> Diagnostics {
+        self.diagnostics.clone()
+    }
+
+    /// Parses every non-blank line, handling failures according to the input [`Mode`].
+    pub fn parsed<T>(self) -> impl Iterator<Item = Result<T, InputError>> + 'a
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let diagnostics = self.diagnostics.clone();
+        self.filter(|line| !line.trim().is_empty())
+            .filter_map(move |line| diagnostics.parse(&line).transpose())
+    }
+
+    /// Parses every non-blank line with `parse`, which positions its own errors, handling
+    /// failures according to the input [`Mode`].
+    pub fn parsed_with<T, F>(self, mut parse: F) -> impl Iterator<Item = Result<T, InputError>> + 'a
+    where
+        F: FnMut(Line<'a>) -> Result<T, InputError> + 'a,
+    {
+        let diagnostics = self.diagnostics.clone();
+        self.filter(|line| !line.trim().is_empty())
+            .filter_map(move |line| diagnostics.check(parse(line)).transpose())
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines
+            .next()
+            .map(|(text, number)| Line { number, text })
+    }
+}
+
+/// A run of consecutive non-blank lines, as separated by blank lines in the input.
+#[derive(Debug, Clone)]
+pub struct Block<'a> {
+    /// Position of the block among the blocks of the input, starting at 0.
+    pub index: usize,
+    /// Number of the first line of the block.
+    pub number: usize,
+    /// The lines of the block, still separated by their line breaks.
+    pub text: &'a str,
+    diagnostics: Diagnostics,
+}
+
+impl<'a> Block<'a> {
+    pub fn lines(&self) -> Lines<'a> {
+        Lines {
+            lines: self.text.lines().zip(self.number..),
+            diagnostics: self.diagnostics.clone(),
+        }
+    }
+
+    /// An error about the whole block, shown at its first line.
+    pub fn error(&self, message: impl Display) -> InputError {
+        let first = self.text.lines().next().unwrap_or_default();
+        InputError::new(
+            self.number,
+            1,
+            first,
+            format!("{message} (block {})", self.index),
+        )
+    }
+
+    /// An error shown where `part`, a slice of the block such as the unparsed rest of it,
+    /// starts, or at the first line if `part` is not part of the block.
+    pub fn error_at(&self, part: &str, message: impl Display) -> InputError {
+        let position = part.as_ptr() as usize;
+        self.lines()
+            .find_map(|line| {
+                let offset = position.checked_sub(line.text.as_ptr() as usize)?;
+                let rest = line.text.get(offset..)?;
+                Some(line.error_at(rest, format!("{message} (block {})", self.index)))
+            })
+            .unwrap_or_else(|| self.error(message))
+    }
+
+    /// Parses the whole text of the block, handling a failure according to the input [`Mode`].
+    pub fn parse<T>(&self) -> Result<Option<T>, InputError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.diagnostics
+            .check(self.text.parse::<T>().map_err(|err| self.error(err)))
+    }
+
+    /// Parses the block with `parse`, which positions its own errors, handling a failure
+    /// according to the input [`Mode`].
+    pub fn parse_with<T>(
+        &self,
+        parse: impl FnOnce(&Self) -> Result<T, InputError>,
+    ) -> Result<Option<T>, InputError> {
+        self.diagnostics.check(parse(self))
+    }
+}
+
+pub struct Blocks<'a> {
+    text: &'a str,
+    lines: Lines<'a>,
+    index: usize,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blank = |line: &Line| line.trim().is_empty();
+
+        let first = self.lines.find(|line| !blank(line))?;
+        let mut last = first;
+        for line in self.lines.by_ref() {
+            if blank(&line) {
+                break;
+            }
+            last = line;
+        }
+
+        // Lines are slices of the input text, so the block spans from the first to the last.
+        let offset = |line: &Line| line.text.as_ptr() as usize - self.text.as_ptr() as usize;
+        let block = Block {
+            index: self.index,
+            number: first.number,
+            text: &self.text[offset(&first)..offset(&last) + last.len()],
+            diagnostics: self.lines.diagnostics(),
+        };
+
+        self.index += 1;
+        Some(block)
+    }
+}
+
+/// A solution that can answer while reading its input incrementally, so that the input may be
+/// arbitrarily large or never end.
+pub trait Streaming: Solution {
+    /// Reads `input` until every part is answered, passing each answer to `report` as soon as it
+    /// is known. Parts left unanswered when the input ends are not reported.
+    fn stream(input: &mut Bytes, report: &mut dyn FnMut(u8, String)) -> Result<(), Error>;
+}
+
+/// A solution that can describe its parsed input in more detail than its two answers.
+pub trait Reporting: Solution {
+    fn report(parsed: &Self::Parsed<'_>, options: &ReportOptions) -> Result<String, InputError>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReportOptions {
+    /// Limits the report to the `top` largest entries.
+    pub top: Option<usize>,
+    pub order: Order,
 }
 
-pub fn input_bytes(path: impl AsRef<Path>) -> io::Result<impl Iterator<Item = u8>> {
-    Ok(BufReader::new(fs::File::open(path)?)
-        .bytes()
-        .filter_map(|byte| byte.ok()))
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Ascending,
+    #[default]
+    Descending,
 }
 
-pub fn static_input_lines(input: &'static str) -> impl Iterator<Item = String> {
-    input.lines().map(|line| line.to_owned())
+/// The bytes of a source, read incrementally through its buffer.
+pub struct Bytes {
+    reader: Box<dyn BufRead>,
+    position: u64,
 }
 
-pub fn static_input_bytes(input: &'static str) -> impl Iterator<Item = u8> {
-    input.bytes()
+impl Bytes {
+    pub fn new(reader: impl BufRead + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            position: 0,
+        }
+    }
+
+    /// Number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl Iterator for Bytes {
+    type Item = io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let byte = loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => break *buf.first()?,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        };
+        self.reader.consume(1);
+        self.position += 1;
+        Some(Ok(byte))
+    }
+}
+
+pub fn input_bytes(source: &Source) -> io::Result<Bytes> {
+    Ok(Bytes {
+        reader: source.open()?,
+        position: 0,
+    })
+}
+
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+    pub skipped: Vec<InputError>,
+}
+
+pub struct Day {
+    pub number: u8,
+    run: fn(Source, Mode, Option<&Config>) -> Result<Answers, Error>,
+    bench: BenchFn,
+    stream: Option<StreamFn>,
+    report: Option<ReportFn>,
+    configurable: bool,
+}
+
+type StreamFn = fn(Source, &mut dyn FnMut(u8, String)) -> Result<u64, Error>;
+type BenchFn = fn(Source, Mode, Option<&Config>, BenchOptions) -> Result<Vec<Timing>, Error>;
+type ReportFn = fn(Source, Mode, Option<&Config>, &ReportOptions) -> Result<String, Error>;
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            number: S::DAY,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+            stream: None,
+            report: None,
+            configurable: false,
+        }
+    }
+
+    /// Marks the day as reading a configuration given alongside its input.
+    pub const fn configurable(self) -> Self {
+        Self {
+            configurable: true,
+            ..self
+        }
+    }
+
+    pub const fn streaming<S: Streaming>() -> Self {
+        Self {
+            stream: Some(stream::<S>),
+            ..Self::of::<S>()
+        }
+    }
+
+    pub const fn reporting<S: Reporting>() -> Self {
+        Self {
+            report: Some(report::<S>),
+            ..Self::of::<S>()
+        }
+    }
+
+    pub fn run(
+        &self,
+        source: Source,
+        mode: Mode,
+        config: Option<&Config>,
+    ) -> Result<Answers, Error> {
+        (self.run)(source, mode, config)
+    }
+
+    /// Times the parse phase and each part separately, over an in-memory copy of the input.
+    pub fn bench(
+        &self,
+        source: Source,
+        mode: Mode,
+        config: Option<&Config>,
+        options: BenchOptions,
+    ) -> Result<Vec<Timing>, Error> {
+        (self.bench)(source, mode, config, options)
+    }
+
+    pub fn can_configure(&self) -> bool {
+        self.configurable
+    }
+
+    pub fn can_report(&self) -> bool {
+        self.report.is_some()
+    }
+
+    pub fn report(
+        &self,
+        source: Source,
+        mode: Mode,
+        config: Option<&Config>,
+        options: &ReportOptions,
+    ) -> Result<String, Error> {
+        match self.report {
+            Some(report) => report(source, mode, config, options),
+            None => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Day {:02} has no report", self.number),
+            )
+            .into()),
+        }
+    }
+
+    pub fn can_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Streams the input through the solution, returning the number of bytes read.
+    pub fn stream(&self, source: Source, mut report: impl FnMut(u8, String)) -> Result<u64, Error> {
+        match self.stream {
+            Some(stream) => stream(source, &mut report),
+            None => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Day {:02} cannot be streamed", self.number),
+            )
+            .into()),
+        }
+    }
+}
+
+fn run<S: Solution>(source: Source, mode: Mode, config: Option<&Config>) -> Result<Answers, Error> {
+    let diagnostics = Diagnostics::new(mode);
+    let with_file = |err: InputError| err.with_file(source.to_string());
+
+    let input = Input::read(&source, &diagnostics)?.with_config(config.cloned());
+    let parsed = S::parse(&input).map_err(with_file)?;
+    let part1 = S::part1(&parsed).map_err(with_file)?;
+    let part2 = S::part2(&parsed).map_err(with_file)?;
+
+    Ok(Answers {
+        part1: part1.to_string(),
+        part2: part2.to_string(),
+        skipped: diagnostics.skipped().into_iter().map(with_file).collect(),
+    })
+}
+
+fn report<S: Reporting>(
+    source: Source,
+    mode: Mode,
+    config: Option<&Config>,
+    options: &ReportOptions,
+) -> Result<String, Error> {
+    let with_file = |err: InputError| err.with_file(source.to_string());
+    let input = Input::read(&source, &Diagnostics::new(mode))?.with_config(config.cloned());
+    let parsed = S::parse(&input).map_err(with_file)?;
+    Ok(S::report(&parsed, options).map_err(with_file)?)
+}
+
+fn stream<S: Streaming>(source: Source, report: &mut dyn FnMut(u8, String)) -> Result<u64, Error> {
+    let mut input = input_bytes(&source)?;
+    S::stream(&mut input, report)?;
+    Ok(input.position())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let input = Input::from("a\r\n\nbc\n");
+        let lines = input.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| (line.number, line.text))
+                .collect::<Vec<_>>(),
+            vec![(1, "a"), (2, ""), (3, "bc")]
+        );
+        // Lines borrow from the input instead of copying it.
+        assert!(input
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&lines[2].text.as_ptr()));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = Input::from("\n1\n2\r\n\n  \n3\n\n\n4\n5");
+        let blocks = input.blocks().collect::<Vec<_>>();
+        assert_eq!(
+            blocks
+                .iter()
+                .map(|block| (block.index, block.number, block.text))
+                .collect::<Vec<_>>(),
+            vec![(0, 2, "1\n2"), (1, 6, "3"), (2, 9, "4\n5")]
+        );
+
+        let lines = blocks[2].lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| (line.number, line.text))
+                .collect::<Vec<_>>(),
+            vec![(9, "4"), (10, "5")]
+        );
+
+        let err = blocks[1].error("Invalid group");
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (6, "Invalid group (block 1)")
+        );
+
+        let err = blocks[2].error_at(&blocks[2].text[2..], "Invalid group");
+        assert_eq!(
+            (
+                err.line,
+                err.column,
+                err.text.as_str(),
+                err.message.as_str()
+            ),
+            (10, 1, "5", "Invalid group (block 2)")
+        );
+        let err = blocks[2].error_at("5", "Invalid group");
+        assert_eq!((err.line, err.column), (9, 1));
+    }
+
+    #[test]
+    fn test_line_error_at() {
+        let input = Input::from("é 12\n");
+        let line = input.lines().next().unwrap();
+        let err = line.error_at(&line.text[3..], "Invalid number");
+        assert_eq!((err.line, err.column), (1, 3));
+        let err = line.error_at(&line.text[line.text.len()..], "Missing number");
+        assert_eq!(err.column, 5);
+        // A slice from elsewhere falls back to the start of the line.
+        let err = line.error_at("12", "Invalid number");
+        assert_eq!(err.column, 1);
+    }
+
+    #[test]
+    fn test_config_entries() {
+        let config = Config::new("rules", "# rules\n\na = 1\n  b=two words # note\n");
+        let entries = config.entries().unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (entry.line.number, entry.key, entry.value))
+                .collect::<Vec<_>>(),
+            vec![(3, "a", "1"), (4, "b", "two words")]
+        );
+        assert_eq!(entries[0].parse::<u8>(), Ok(1));
+
+        let err = entries[1].parse::<u8>().unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (4, 5, "Invalid b: invalid digit found in string")
+        );
+        assert_eq!(config.missing("Missing c").line, 5);
+
+        let err = Config::new("rules", "a = 1\na = 2\n")
+            .entries()
+            .unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "Duplicate key a, already defined on line 1")
+        );
+        let err = Config::new("rules", "a\n").entries().unwrap_err();
+        assert_eq!(err.message, "Expected key = value");
+
+        let config = Config::new("rules", "a = \" \"\nb = \"#\" # note\nc = \"\"\n");
+        let values = config
+            .entries()
+            .unwrap()
+            .iter()
+            .map(|entry| entry.value)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![" ", "#", ""]);
+
+        let error = |text| {
+            let err = Config::new("rules", text).entries().unwrap_err();
+            (err.column, err.message)
+        };
+        assert_eq!(error("a = \"#\n"), (5, "Unclosed quote".to_owned()));
+        assert_eq!(
+            error("a = \"b\" c\n"),
+            (9, "Unexpected text after the quoted value".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_utf8() {
+        let source = Source::memory([b'a', 0xff]);
+        let err = Input::read(&source, &Diagnostics::default()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("<memory>: "));
+    }
 }
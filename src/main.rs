@@ -0,0 +1,555 @@
+use std::{env, ops::RangeInclusive, process::ExitCode, time::Instant};
+
+use advent::{
+    AnswerFile, BenchOptions, Comparison, Config, Day, History, InputError, Mode, Order,
+    ReportOptions, Source, Timing,
+};
+use days::DAYS;
+
+mod days;
+
+const USAGE: &str =
+    "usage: advent [--lenient] [--config FILE] [--verify | --record | --bench [--warmup N] \
+    [--iterations N] [--json] | --compare [--baseline COMMIT] [--threshold PCT] | \
+    --stream [--throughput] | --report [--top K] [--ascending | --descending]] \
+    [all | DAY | FIRST..LAST] [INPUT | -]";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Verify,
+    Record,
+    Bench,
+    Compare,
+    Stream,
+    Report,
+}
+
+fn main() -> ExitCode {
+    let mut command = Command::Run;
+    let mut mode = Mode::Strict;
+    let mut bench = BenchOptions::default();
+    let mut json = false;
+    let mut throughput = false;
+    let mut report = ReportOptions::default();
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut config = None;
+    let mut selected = None;
+    let mut input = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut count = |flag: &str| {
+            args.next()
+                .and_then(|value| value.parse::<usize>().ok())
+                .ok_or_else(|| format!("{flag} expects a number"))
+        };
+
+        let parsed = match arg.as_str() {
+            "--lenient" => {
+                mode = Mode::Lenient;
+                Ok(())
+            }
+            "--strict" => {
+                mode = Mode::Strict;
+                Ok(())
+            }
+            "--config" => args
+                .next()
+                .map(|path| config = Some(path))
+                .ok_or_else(|| "--config expects a file".to_owned()),
+            "--verify" => {
+                command = Command::Verify;
+                Ok(())
+            }
+            "--record" => {
+                command = Command::Record;
+                Ok(())
+            }
+            "--bench" => {
+                command = Command::Bench;
+                Ok(())
+            }
+            "--compare" => {
+                command = Command::Compare;
+                Ok(())
+            }
+            "--baseline" => args
+                .next()
+                .map(|commit| baseline = Some(commit))
+                .ok_or_else(|| "--baseline expects a commit".to_owned()),
+            "--threshold" => args
+                .next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|pct| *pct >= 0.0)
+                .map(|pct| threshold = pct)
+                .ok_or_else(|| "--threshold expects a percentage".to_owned()),
+            "--stream" => {
+                command = Command::Stream;
+                Ok(())
+            }
+            "--throughput" => {
+                throughput = true;
+                Ok(())
+            }
+            "--report" => {
+                command = Command::Report;
+                Ok(())
+            }
+            "--top" => count("--top").map(|top| report.top = Some(top)),
+            "--ascending" => {
+                report.order = Order::Ascending;
+                Ok(())
+            }
+            "--descending" => {
+                report.order = Order::Descending;
+                Ok(())
+            }
+            "--warmup" => count("--warmup").map(|warmup| bench.warmup = warmup),
+            "--iterations" => count("--iterations").map(|iterations| bench.iterations = iterations),
+            "--json" => {
+                json = true;
+                Ok(())
+            }
+            arg if selected.is_none() => parse_days(arg).map(|days| selected = Some(days)),
+            arg if input.is_none() => arg
+                .parse::<Source>()
+                .map(|source| input = Some(source))
+                .map_err(|err| err.to_string()),
+            arg => Err(format!("Unexpected argument: {arg}")),
+        };
+
+        if let Err(err) = parsed {
+            eprintln!("{err}");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    }
+
+    let selected = selected.unwrap_or(1..=u8::MAX);
+    let days = DAYS
+        .iter()
+        .filter(|day| selected.contains(&day.number))
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        eprintln!("No solutions for {selected:?}");
+        return ExitCode::from(2);
+    }
+
+    if input.is_some() && days.len() > 1 {
+        eprintln!("An input can only be given when running a single day");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if config.is_some() && days.len() > 1 {
+        eprintln!("A configuration can only be given when running a single day");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if config.is_some() && !days[0].can_configure() {
+        eprintln!("Day {:02} does not take a configuration", days[0].number);
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if config.is_some() && !matches!(command, Command::Run | Command::Bench | Command::Report) {
+        eprintln!("A configuration can only be given to run, benchmark or report on a day");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if command == Command::Stream && (days.len() > 1 || !days[0].can_stream()) {
+        eprintln!("Only a single day supporting streaming can be streamed");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if command == Command::Report && (days.len() > 1 || !days[0].can_report()) {
+        eprintln!("Only a single day with a report can be reported on");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if input.is_some() && matches!(command, Command::Verify | Command::Record) {
+        eprintln!("Answers can only be verified or recorded for the puzzle inputs");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    if input.is_some() && command == Command::Compare {
+        eprintln!("Only benchmarks of the puzzle inputs are compared");
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+
+    let config = match config.map(Config::read).transpose() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        Command::Run => run(&days, input, mode, config.as_ref()),
+        Command::Verify => verify(&days, mode),
+        Command::Record => record(&days, mode),
+        Command::Bench => benchmark(&days, input, mode, config.as_ref(), bench, json),
+        Command::Compare => compare(&days, baseline.as_deref(), threshold / 100.0),
+        Command::Stream => stream(days[0], input, throughput),
+        Command::Report => print_report(days[0], input, mode, config.as_ref(), &report),
+    }
+}
+
+fn run(days: &[&Day], input: Option<Source>, mode: Mode, config: Option<&Config>) -> ExitCode {
+    let mut skipped = Vec::new();
+
+    for day in days {
+        let source = input.clone().unwrap_or_else(|| Source::puzzle(day.number));
+        match day.run(source, mode, config) {
+            Ok(answers) => {
+                println!("Day {:02}", day.number);
+                print_answer("Part1", &answers.part1);
+                print_answer("Part2", &answers.part2);
+                skipped.extend(answers.skipped);
+            }
+            Err(err) => {
+                eprintln!("Day {:02}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    print_skipped(&skipped);
+
+    ExitCode::SUCCESS
+}
+
+fn verify(days: &[&Day], mode: Mode) -> ExitCode {
+    let path = AnswerFile::default_path();
+    let expected = match AnswerFile::load(&path) {
+        Ok(expected) => expected,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failed = false;
+    let mut skipped = Vec::new();
+
+    println!("Day  Part Status   Answer");
+
+    for day in days {
+        let answers = match day.run(Source::puzzle(day.number), mode, None) {
+            Ok(answers) => answers,
+            Err(err) => {
+                println!(
+                    "{:02}   {:<4} {:<8} {}",
+                    day.number,
+                    "-",
+                    "ERROR",
+                    summary(&err.to_string())
+                );
+                failed = true;
+                continue;
+            }
+        };
+
+        for (part, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+            let (status, note) = match expected.get(day.number, part) {
+                Some(expected) if expected == answer => ("pass", String::new()),
+                Some(expected) => {
+                    failed = true;
+                    ("FAIL", format!(" (expected {})", summary(expected)))
+                }
+                None => ("missing", String::new()),
+            };
+            println!(
+                "{:02}   {:<4} {:<8} {}{note}",
+                day.number,
+                part,
+                status,
+                summary(answer)
+            );
+        }
+
+        skipped.extend(answers.skipped);
+    }
+
+    print_skipped(&skipped);
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn record(days: &[&Day], mode: Mode) -> ExitCode {
+    let path = AnswerFile::default_path();
+    let mut answers = match AnswerFile::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for day in days {
+        match day.run(Source::puzzle(day.number), mode, None) {
+            Ok(recorded) => {
+                answers.set(day.number, 1, recorded.part1);
+                answers.set(day.number, 2, recorded.part2);
+            }
+            Err(err) => {
+                eprintln!("Day {:02}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if let Err(err) = answers.save(&path) {
+        eprintln!("{}: {err}", path.display());
+        return ExitCode::FAILURE;
+    }
+
+    println!(
+        "Recorded answers for {} day(s) in {}",
+        days.len(),
+        path.display()
+    );
+    ExitCode::SUCCESS
+}
+
+fn benchmark(
+    days: &[&Day],
+    input: Option<Source>,
+    mode: Mode,
+    config: Option<&Config>,
+    options: BenchOptions,
+    json: bool,
+) -> ExitCode {
+    let mut timings = Vec::new();
+
+    for day in days {
+        let source = input.clone().unwrap_or_else(|| Source::puzzle(day.number));
+        match day.bench(source, mode, config, options) {
+            Ok(timing) => timings.extend(timing),
+            Err(err) => {
+                eprintln!("Day {:02}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    // Only the puzzle inputs are tracked, so that runs stay comparable.
+    if input.is_none() && config.is_none() {
+        let path = History::default_path();
+        if let Err(err) = History::append(&path, &History::record(timings.clone())) {
+            eprintln!("{}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if json {
+        match serde_json::to_string_pretty(&timings) {
+            Ok(json) => println!("{json}"),
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        print_timings(&timings);
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn stream(day: &Day, input: Option<Source>, throughput: bool) -> ExitCode {
+    let source = input.unwrap_or_else(|| Source::puzzle(day.number));
+    let start = Instant::now();
+    let mut answered = Vec::new();
+
+    println!("Day {:02}", day.number);
+    let result = day.stream(source, |part, answer| {
+        print_answer(&format!("Part{part}"), &answer);
+        answered.push(part);
+    });
+
+    let read = match result {
+        Ok(read) => read,
+        Err(err) => {
+            eprintln!("Day {:02}: {err}", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+    let elapsed = start.elapsed();
+
+    for part in [1, 2].into_iter().filter(|part| !answered.contains(part)) {
+        eprintln!("Part{part}: no answer before the end of the input");
+    }
+
+    if throughput {
+        eprintln!(
+            "Read {read} bytes in {elapsed:.2?} ({:.0} bytes/s)",
+            read as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn print_report(
+    day: &Day,
+    input: Option<Source>,
+    mode: Mode,
+    config: Option<&Config>,
+    options: &ReportOptions,
+) -> ExitCode {
+    let source = input.unwrap_or_else(|| Source::puzzle(day.number));
+    match day.report(source, mode, config, options) {
+        Ok(report) => {
+            print!("{report}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Day {:02}: {err}", day.number);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn compare(days: &[&Day], baseline: Option<&str>, threshold: f64) -> ExitCode {
+    let path = History::default_path();
+    let history = match History::load(&path) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let runs = history.runs();
+    let Some(&candidate) = runs.last() else {
+        eprintln!("No benchmark runs recorded in {}", path.display());
+        return ExitCode::FAILURE;
+    };
+
+    let baseline = match baseline {
+        Some(commit) => history.find_run(commit, candidate),
+        None => {
+            let days = days.iter().map(|day| day.number).collect::<Vec<_>>();
+            history.previous_run(candidate, &days)
+        }
+    };
+    let Some(baseline) = baseline else {
+        eprintln!("No baseline run to compare against in {}", path.display());
+        return ExitCode::FAILURE;
+    };
+
+    println!(
+        "Comparing {} against baseline {}",
+        history.commit(candidate).unwrap_or_default(),
+        history.commit(baseline).unwrap_or_default()
+    );
+
+    let comparisons = history
+        .compare(baseline, candidate, threshold)
+        .into_iter()
+        .filter(|comparison| days.iter().any(|day| day.number == comparison.day))
+        .collect::<Vec<_>>();
+    if comparisons.is_empty() {
+        eprintln!("The baseline and candidate runs have no day and phase in common");
+        return ExitCode::FAILURE;
+    }
+    print_comparisons(&comparisons);
+
+    if comparisons.iter().any(|comparison| comparison.regressed) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn print_comparisons(comparisons: &[Comparison]) {
+    println!(
+        "Day  Phase  {:>12} {:>12} {:>8}  Status",
+        "Baseline", "Median", "Change"
+    );
+    for comparison in comparisons {
+        println!(
+            "{:02}   {:<6} {:>12} {:>12} {:>8}  {}",
+            comparison.day,
+            comparison.phase.name(),
+            format!("{:.2?}", comparison.baseline),
+            format!("{:.2?}", comparison.candidate),
+            format!("{:+.1}%", comparison.change() * 100.0),
+            if comparison.regressed {
+                "REGRESSED"
+            } else {
+                "ok"
+            },
+        );
+    }
+}
+
+fn print_timings(timings: &[Timing]) {
+    println!(
+        "Day  Phase  Iters  {:>12} {:>12} {:>12} {:>12}",
+        "Min", "Median", "Mean", "Stddev"
+    );
+    for timing in timings {
+        let stats = &timing.stats;
+        println!(
+            "{:02}   {:<6} {:<6} {:>12} {:>12} {:>12} {:>12}",
+            timing.day,
+            timing.phase.name(),
+            timing.iterations,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev),
+        );
+    }
+}
+
+fn print_skipped(skipped: &[InputError]) {
+    if !skipped.is_empty() {
+        eprintln!("\nSkipped {} malformed line(s):", skipped.len());
+        for err in skipped {
+            eprintln!("{err}");
+        }
+    }
+}
+
+/// First line of an answer, for single-line tables.
+fn summary(answer: &str) -> String {
+    match answer.split_once('\n') {
+        Some((first, _)) => format!("{first} ..."),
+        None => answer.to_owned(),
+    }
+}
+
+fn print_answer(part: &str, answer: &str) {
+    if answer.contains('\n') {
+        println!("{part}:\n{answer}");
+    } else {
+        println!("{part}: {answer}");
+    }
+}
+
+fn parse_days(arg: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse = |day: &str| day.parse::<u8>().map_err(|_| format!("Invalid day: {day}"));
+
+    match arg.split_once("..") {
+        _ if arg == "all" => Ok(1..=u8::MAX),
+        Some((first, last)) => Ok(parse(first)?..=parse(last.trim_start_matches('='))?),
+        None => parse(arg).map(|day| day..=day),
+    }
+}
@@ -0,0 +1,206 @@
+//! Detection of markers, runs of pairwise distinct symbols, in a stream.
+//!
+//! Each symbol is processed in constant time by keeping a count of every symbol in the current
+//! window, so a stream can be scanned for any window size without revisiting past symbols.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A symbol of a datastream, with the structure used to count the symbols in a window.
+pub trait Symbol: Copy {
+    type Counts: Counts<Self>;
+}
+
+pub trait Counts<T>: Default {
+    /// Increments the count of `symbol`, returning the new count.
+    fn increment(&mut self, symbol: T) -> usize;
+    /// Decrements the count of `symbol`, returning the new count.
+    fn decrement(&mut self, symbol: T) -> usize;
+}
+
+impl Symbol for u8 {
+    type Counts = ByteCounts;
+}
+
+impl Symbol for char {
+    type Counts = HashCounts<char>;
+}
+
+/// Counts for the whole `u8` alphabet, in a fixed table.
+pub struct ByteCounts([usize; 256]);
+
+impl Default for ByteCounts {
+    fn default() -> Self {
+        Self([0; 256])
+    }
+}
+
+impl Counts<u8> for ByteCounts {
+    fn increment(&mut self, symbol: u8) -> usize {
+        let count = &mut self.0[symbol as usize];
+        *count += 1;
+        *count
+    }
+
+    fn decrement(&mut self, symbol: u8) -> usize {
+        let count = &mut self.0[symbol as usize];
+        *count -= 1;
+        *count
+    }
+}
+
+/// Counts for an open alphabet, such as `char`.
+pub struct HashCounts<T>(HashMap<T, usize>);
+
+impl<T> Default for HashCounts<T> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<T: Eq + Hash> Counts<T> for HashCounts<T> {
+    fn increment(&mut self, symbol: T) -> usize {
+        let count = self.0.entry(symbol).or_default();
+        *count += 1;
+        *count
+    }
+
+    fn decrement(&mut self, symbol: T) -> usize {
+        let count = self.0.get_mut(&symbol).expect("symbol in window");
+        *count -= 1;
+        let count = *count;
+        if count == 0 {
+            self.0.remove(&symbol);
+        }
+        count
+    }
+}
+
+/// Tracks the last `size` symbols of a stream and whether they are pairwise distinct.
+pub struct MarkerDetector<T: Symbol> {
+    size: usize,
+    window: VecDeque<T>,
+    counts: T::Counts,
+    /// Number of symbols occurring more than once in the window.
+    repeated: usize,
+    position: usize,
+}
+
+impl<T: Symbol> MarkerDetector<T> {
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "marker size must be positive");
+        Self {
+            size,
+            window: VecDeque::with_capacity(size),
+            counts: T::Counts::default(),
+            repeated: 0,
+            position: 0,
+        }
+    }
+
+    /// Feeds the next symbol, returning whether it completes a marker.
+    pub fn push(&mut self, symbol: T) -> bool {
+        if self.window.len() == self.size {
+            if let Some(oldest) = self.window.pop_front() {
+                if self.counts.decrement(oldest) == 1 {
+                    self.repeated -= 1;
+                }
+            }
+        }
+
+        if self.counts.increment(symbol) == 2 {
+            self.repeated += 1;
+        }
+        self.window.push_back(symbol);
+        self.position += 1;
+
+        self.window.len() == self.size && self.repeated == 0
+    }
+
+    /// Number of symbols fed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+/// Positions just after every marker of `size` symbols, counted in symbols from the start.
+pub fn markers<I>(symbols: I, size: usize) -> impl Iterator<Item = usize>
+where
+    I: IntoIterator,
+    I::Item: Symbol,
+{
+    let mut detector = MarkerDetector::new(size);
+    symbols
+        .into_iter()
+        .zip(1..)
+        .filter(move |(symbol, _)| detector.push(*symbol))
+        .map(|(_, position)| position)
+}
+
+/// Position just after the first marker of `size` symbols, counted in symbols from the start.
+pub fn first_marker<I>(symbols: I, size: usize) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Symbol,
+{
+    markers(symbols, size).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_first_marker() {
+        let stream = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        assert_eq!(first_marker(stream.iter().copied(), 4), Some(7));
+        assert_eq!(first_marker(stream.iter().copied(), 14), Some(19));
+        assert_eq!(first_marker(stream.iter().copied(), 1), Some(1));
+        assert_eq!(first_marker(b"aaaa".iter().copied(), 2), None);
+    }
+
+    #[test]
+    fn test_all_markers() {
+        let markers = markers(b"abcabbc".iter().copied(), 3).collect::<Vec<_>>();
+        assert_eq!(markers, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_chars() {
+        // Positions count chars rather than bytes.
+        assert_eq!(first_marker("ééàéàü".chars(), 3), Some(6));
+    }
+
+    fn naive<T: Eq + Hash>(stream: &[T], size: usize) -> Vec<usize> {
+        stream
+            .windows(size)
+            .enumerate()
+            .filter(|(_, window)| window.iter().collect::<HashSet<_>>().len() == size)
+            .map(|(start, _)| start + size)
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_markers(stream in prop::collection::vec(0..12u8, 0..256), size in 1..14usize) {
+            let found = markers(stream.iter().copied(), size).collect::<Vec<_>>();
+            prop_assert_eq!(found, naive(&stream, size));
+        }
+
+        #[test]
+        fn prop_char_markers(stream in "[a-cé€😀]{0,64}", size in 1..6usize) {
+            let chars = stream.chars().collect::<Vec<_>>();
+            let found = markers(stream.chars(), size).collect::<Vec<_>>();
+            prop_assert_eq!(found, naive(&chars, size));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    convert::Infallible,
+    fmt::{self, Display},
+    fs,
+    io::{self, BufRead, BufReader, Cursor},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Memory(Arc<[u8]>),
+}
+
+impl Source {
+    /// The recorded input for a day, under `inputs/` at the crate root.
+    pub fn puzzle(day: u8) -> Self {
+        Source::File(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("inputs")
+                .join(format!("day-{day:02}.txt")),
+        )
+    }
+
+    pub fn memory(input: impl AsRef<[u8]>) -> Self {
+        Source::Memory(input.as_ref().into())
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => fs::File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display()))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::Memory(buf) => Ok(Box::new(Cursor::new(buf.clone()))),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => path.display().fmt(f),
+            Source::Stdin => f.write_str("<stdin>"),
+            Source::Memory(_) => f.write_str("<memory>"),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = Infallible;
+
+    /// Parses a command line argument, where `-` stands for stdin.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "-" => Ok(Source::Stdin),
+            path => Ok(Source::File(path.into())),
+        }
+    }
+}
+
+impl From<PathBuf> for Source {
+    fn from(path: PathBuf) -> Self {
+        Source::File(path)
+    }
+}
+
+impl From<&Path> for Source {
+    fn from(path: &Path) -> Self {
+        Source::File(path.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("-".parse(), Ok(Source::Stdin));
+        assert_eq!("day.txt".parse(), Ok(Source::File("day.txt".into())));
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let Source::File(path) = Source::puzzle(7) else {
+            panic!("expected a file source");
+        };
+        assert!(path.is_absolute());
+        assert!(path.ends_with("inputs/day-07.txt"));
+    }
+
+    #[test]
+    fn test_memory_reopens() {
+        let source = Source::memory("a\nb\n");
+        for _ in 0..2 {
+            let lines = source
+                .open()
+                .unwrap()
+                .lines()
+                .collect::<Result<Vec<_>, _>>();
+            assert_eq!(lines.unwrap(), ["a", "b"]);
+        }
+    }
+}